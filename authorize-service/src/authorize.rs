@@ -22,9 +22,26 @@ thread_local! {
 }
 
 pub fn authorize<N: Network>(bytes: Bytes) -> Result<Value> {
+    // Compute the `Authorization`.
+    with_process::<N, _>(|process| process.authorize(&bytes))
+}
+
+/// Loads the process on every worker of the global rayon pool.
+pub fn warm_up_workers<N: Network>() -> Result<()> {
+    rayon::broadcast(|_| with_process::<N, _>(|_| Ok(())))
+        .into_iter()
+        .collect()
+}
+
+/// Runs `f` against the thread-local process, initializing it if necessary.
+fn with_process<N: Network, T>(f: impl FnOnce(&ProcessVariant) -> Result<T>) -> Result<T> {
     PROCESS.with(|process| {
         // Initialize the process if it is not already initialized.
         if process.borrow().is_none() {
+            let _timer = metrics()
+                .process_load_duration
+                .with_label_values(&[N::NAME])
+                .start_timer();
             *process.borrow_mut() = match N::ID {
                 MainnetV0::ID => {
                     println!("Loading mainnet process...");
@@ -40,8 +57,9 @@ pub fn authorize<N: Network>(bytes: Bytes) -> Result<Value> {
                 }
                 _ => panic!("Invalid network"),
             };
+            metrics().loaded_processes.inc();
+            record_loaded_process();
         };
-        // Compute the `Authorization`.
-        process.borrow().as_ref().unwrap().authorize(&bytes)
+        f(process.borrow().as_ref().unwrap())
     })
 }
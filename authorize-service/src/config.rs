@@ -0,0 +1,128 @@
+// Copyright (C) 2019-2024 Aleo Systems Inc.
+// This file is part of the Aleo SDK library.
+
+// The Aleo SDK library is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// The Aleo SDK library is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with the Aleo SDK library. If not, see <https://www.gnu.org/licenses/>.
+
+use super::*;
+
+use std::net::IpAddr;
+
+/// The `[authorize]` section of the configuration file.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct AuthorizeConfig {
+    /// The address the HTTP and gRPC servers bind to.
+    pub bind: IpAddr,
+    /// The port the HTTP server listens on.
+    pub port: u16,
+    /// The port of the gRPC server, if it is enabled.
+    pub grpc_port: Option<u16>,
+    /// The maximum request body size in bytes.
+    pub body_limit: u64,
+    /// The number of worker threads. Defaults to one per CPU.
+    pub threads: Option<usize>,
+    /// How long, in seconds, to wait for in-flight jobs on shutdown.
+    pub shutdown_timeout_secs: u64,
+}
+
+impl Default for AuthorizeConfig {
+    fn default() -> Self {
+        Self {
+            bind: IpAddr::from([127, 0, 0, 1]),
+            port: 8080,
+            grpc_port: None,
+            body_limit: 32 * 1024, // 32 KiB
+            threads: None,
+            shutdown_timeout_secs: 300,
+        }
+    }
+}
+
+impl ServiceConfig for AuthorizeConfig {
+    const SECTION: &'static str = "authorize";
+
+    fn apply_env(&mut self) -> Result<()> {
+        env_value("ALEO_AUTHORIZE_BIND", &mut self.bind)?;
+        env_value("ALEO_AUTHORIZE_PORT", &mut self.port)?;
+        env_option("ALEO_AUTHORIZE_GRPC_PORT", &mut self.grpc_port)?;
+        env_value("ALEO_AUTHORIZE_BODY_LIMIT", &mut self.body_limit)?;
+        env_option("ALEO_AUTHORIZE_THREADS", &mut self.threads)?;
+        env_value(
+            "ALEO_AUTHORIZE_SHUTDOWN_TIMEOUT_SECS",
+            &mut self.shutdown_timeout_secs,
+        )?;
+        Ok(())
+    }
+
+    fn validate(&self) -> Result<()> {
+        ensure!(self.port != 0, "The authorize port must not be 0");
+        ensure!(
+            self.grpc_port != Some(self.port),
+            "The authorize gRPC port must differ from the HTTP port"
+        );
+        ensure!(
+            self.body_limit > 0,
+            "The authorize body limit must not be 0"
+        );
+        ensure!(
+            self.threads != Some(0),
+            "The authorize thread count must not be 0"
+        );
+        Ok(())
+    }
+}
+
+impl AuthorizeConfig {
+    /// Returns the settings shared by the routes.
+    pub fn settings(&self) -> Settings {
+        Settings {
+            body_limit: self.body_limit,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_default_config_is_valid() {
+        assert!(AuthorizeConfig::default().validate().is_ok());
+    }
+
+    #[test]
+    fn test_validate_rejects_invalid_values() {
+        let invalid = [
+            AuthorizeConfig {
+                port: 0,
+                ..Default::default()
+            },
+            AuthorizeConfig {
+                grpc_port: Some(8080),
+                ..Default::default()
+            },
+            AuthorizeConfig {
+                body_limit: 0,
+                ..Default::default()
+            },
+            AuthorizeConfig {
+                threads: Some(0),
+                ..Default::default()
+            },
+        ];
+        for config in invalid {
+            assert!(config.validate().is_err(), "{config:?}");
+        }
+    }
+}
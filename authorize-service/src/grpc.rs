@@ -0,0 +1,213 @@
+// Copyright (C) 2019-2024 Aleo Systems Inc.
+// This file is part of the Aleo SDK library.
+
+// The Aleo SDK library is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// The Aleo SDK library is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with the Aleo SDK library. If not, see <https://www.gnu.org/licenses/>.
+
+use super::*;
+
+use snarkvm::prelude::{Identifier, ProgramID, Value as AleoValue, U64};
+use std::future::Future;
+use std::marker::PhantomData;
+use std::net::SocketAddr;
+use tokio::net::TcpListener;
+use tokio_stream::wrappers::TcpListenerStream;
+use tonic::{Request, Response, Status};
+
+pub mod proto {
+    tonic::include_proto!("authorize");
+}
+
+use proto::authorize_service_server::{AuthorizeService, AuthorizeServiceServer};
+
+/// The gRPC interface of the authorize service, backed by the same handlers as the HTTP routes.
+pub struct GrpcService<N: Network> {
+    _network: PhantomData<N>,
+}
+
+impl<N: Network> Default for GrpcService<N> {
+    fn default() -> Self {
+        Self {
+            _network: PhantomData,
+        }
+    }
+}
+
+#[tonic::async_trait]
+impl<N: Network> AuthorizeService for GrpcService<N> {
+    async fn keygen(
+        &self,
+        request: Request<proto::KeygenRequest>,
+    ) -> Result<Response<proto::KeygenResponse>, Status> {
+        let seed = request.into_inner().seed;
+        let response = run("grpc_keygen", move || keygen::<N>(&seed)).await?;
+        Ok(Response::new(proto::KeygenResponse {
+            private_key: response.private_key,
+            address: response.address,
+        }))
+    }
+
+    async fn authorize(
+        &self,
+        request: Request<proto::AuthorizeRequest>,
+    ) -> Result<Response<proto::AuthorizeResponse>, Status> {
+        let request = request.into_inner();
+        // Encode the request as the JSON body expected by `authorize`.
+        let request = AuthorizeRequest::<N> {
+            private_key: parse(&request.private_key)?,
+            program_id: parse::<ProgramID<N>>(&request.program_id)?,
+            function_name: parse::<Identifier<N>>(&request.function_name)?,
+            inputs: request
+                .inputs
+                .iter()
+                .map(|input| parse::<AleoValue<N>>(input))
+                .collect::<Result<_, _>>()?,
+            base_fee_in_microcredits: U64::new(request.base_fee_in_microcredits),
+            priority_fee_in_microcredits: U64::new(request.priority_fee_in_microcredits),
+        };
+        let bytes = serde_json::to_vec(&request)
+            .map_err(|error| Status::invalid_argument(error.to_string()))?;
+        let response = run("grpc_authorize", || authorize::<N>(Bytes::from(bytes))).await?;
+        Ok(Response::new(proto::AuthorizeResponse {
+            function_authorization: response["function_authorization"].to_string(),
+            fee_authorization: response["fee_authorization"].to_string(),
+        }))
+    }
+
+    async fn sign(
+        &self,
+        request: Request<proto::SignRequest>,
+    ) -> Result<Response<proto::SignResponse>, Status> {
+        let request = request.into_inner();
+        let request = SignRequest::<N> {
+            private_key: parse(&request.private_key)?,
+            message: request.message,
+        };
+        let response = run("grpc_sign", || sign::<N>(request)).await?;
+        Ok(Response::new(proto::SignResponse {
+            signed_message: response.signed_message,
+        }))
+    }
+
+    async fn verify(
+        &self,
+        request: Request<proto::VerifyRequest>,
+    ) -> Result<Response<proto::VerifyResponse>, Status> {
+        let request = request.into_inner();
+        let request = VerifyRequest::<N> {
+            address: parse(&request.address)?,
+            message: request.message,
+            signature: parse(&request.signature)?,
+        };
+        let response = run("grpc_verify", || verify::<N>(request)).await?;
+        Ok(Response::new(proto::VerifyResponse {
+            result: response.result,
+        }))
+    }
+}
+
+/// Parses a request field, reporting a failure as an invalid argument.
+fn parse<T: FromStr<Err = anyhow::Error>>(value: &str) -> Result<T, Status> {
+    T::from_str(value).map_err(|error| Status::invalid_argument(error.to_string()))
+}
+
+/// Runs a handler on the rayon pool and records the outcome of the request to `route`.
+async fn run<T: Send + 'static>(
+    route: &str,
+    f: impl FnOnce() -> Result<T> + Send + 'static,
+) -> Result<T, Status> {
+    if is_draining() {
+        return Err(Status::unavailable("The service is shutting down"));
+    }
+    match spawn_worker(metrics(), f).await {
+        Ok(response) => {
+            metrics().record_request(route, "success");
+            Ok(response)
+        }
+        Err(error) => {
+            metrics().record_request(route, "error");
+            Err(Status::internal(error.to_string()))
+        }
+    }
+}
+
+/// Binds the gRPC interface to `address`, returning the bound address and the server.
+/// The server stops accepting requests once the service starts shutting down.
+pub async fn bind_grpc<N: Network>(
+    address: SocketAddr,
+) -> Result<(SocketAddr, impl Future<Output = Result<()>>)> {
+    let listener = TcpListener::bind(address).await?;
+    let address = listener.local_addr()?;
+    let service = AuthorizeServiceServer::new(GrpcService::<N>::default())
+        .max_decoding_message_size(settings().body_limit as usize);
+    let server = async move {
+        tonic::transport::Server::builder()
+            .add_service(service)
+            .serve_with_incoming_shutdown(TcpListenerStream::new(listener), wait_for_draining())
+            .await?;
+        Ok(())
+    };
+    Ok((address, server))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use proto::authorize_service_client::AuthorizeServiceClient;
+    use tonic::Code;
+
+    async fn spawn_grpc() -> AuthorizeServiceClient<tonic::transport::Channel> {
+        let (address, server) = bind_grpc::<TestnetV0>(([127, 0, 0, 1], 0).into())
+            .await
+            .unwrap();
+        tokio::spawn(server);
+        AuthorizeServiceClient::connect(format!("http://{address}"))
+            .await
+            .unwrap()
+    }
+
+    #[tokio::test]
+    async fn test_grpc_keygen_matches_http_handler() {
+        let mut client = spawn_grpc().await;
+        let request = proto::KeygenRequest {
+            seed: "1".to_string(),
+        };
+        let response = client.keygen(request).await.unwrap().into_inner();
+        let expected = keygen::<TestnetV0>("1").unwrap();
+        assert_eq!(response.private_key, expected.private_key);
+        assert_eq!(response.address, expected.address);
+    }
+
+    #[tokio::test]
+    async fn test_grpc_rejects_invalid_argument() {
+        let mut client = spawn_grpc().await;
+        let request = proto::AuthorizeRequest {
+            private_key: "not a private key".to_string(),
+            ..Default::default()
+        };
+        let status = client.authorize(request).await.unwrap_err();
+        assert_eq!(status.code(), Code::InvalidArgument);
+    }
+
+    #[tokio::test]
+    async fn test_grpc_rejects_request_over_body_limit() {
+        let mut client = spawn_grpc().await;
+        let request = proto::SignRequest {
+            message: vec![0; settings().body_limit as usize + 1],
+            ..Default::default()
+        };
+        let status = client.sign(request).await.unwrap_err();
+        assert_eq!(status.code(), Code::OutOfRange);
+    }
+}
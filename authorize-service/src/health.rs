@@ -0,0 +1,51 @@
+// Copyright (C) 2019-2024 Aleo Systems Inc.
+// This file is part of the Aleo SDK library.
+
+// The Aleo SDK library is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// The Aleo SDK library is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with the Aleo SDK library. If not, see <https://www.gnu.org/licenses/>.
+
+use super::*;
+
+use std::collections::BTreeSet;
+use std::sync::Mutex;
+
+// The rayon workers that have loaded their process.
+static LOADED_WORKERS: Mutex<BTreeSet<usize>> = Mutex::new(BTreeSet::new());
+
+/// Records that the calling worker thread has loaded its process.
+/// Calls made outside of the rayon pool are ignored.
+pub fn record_loaded_process() {
+    if let Some(worker) = rayon::current_thread_index() {
+        LOADED_WORKERS.lock().unwrap().insert(worker);
+    }
+}
+
+/// Reports whether every worker has loaded its process.
+pub fn readiness<N: Network>() -> ReadinessResponse {
+    let loaded = LOADED_WORKERS.lock().unwrap().clone();
+    let workers = (0..rayon::current_num_threads())
+        .map(|worker| WorkerReadiness {
+            worker,
+            process_loaded: loaded.contains(&worker),
+        })
+        .collect::<Vec<_>>();
+    let draining = is_draining();
+    ReadinessResponse {
+        ready: !draining && workers.iter().all(|worker| worker.process_loaded),
+        draining,
+        network: N::NAME.to_string(),
+        version: env!("CARGO_PKG_VERSION").to_string(),
+        queue_depth: metrics().queue_depth.get(),
+        workers,
+    }
+}
@@ -17,9 +17,23 @@
 pub mod authorize;
 pub use authorize::*;
 
+pub mod config;
+pub use config::*;
+
+#[cfg(feature = "grpc")]
+pub mod grpc;
+#[cfg(feature = "grpc")]
+pub use grpc::*;
+
+pub mod health;
+pub use health::*;
+
 pub mod keygen;
 pub use keygen::*;
 
+pub mod metrics;
+pub use metrics::*;
+
 pub mod process_variant;
 pub use process_variant::*;
 
@@ -32,6 +46,13 @@ pub use response::*;
 pub mod routes;
 pub use routes::*;
 
+pub mod settings;
+pub use settings::*;
+
+pub use service_common::config::*;
+pub use service_common::metrics::*;
+pub use service_common::shutdown::*;
+
 pub mod signature;
 pub use signature::*;
 
@@ -41,7 +62,7 @@ use snarkvm::prelude::{
     PrivateKey, Process, Serialize, Signature, TestnetV0, ToBytes,
 };
 
-use anyhow::Result;
+use anyhow::{anyhow, ensure, Result};
 use rand_chacha::rand_core::SeedableRng;
 use serde_json::Value;
 use std::cell::RefCell;
@@ -17,39 +17,179 @@
 use authorize_service::*;
 use snarkvm::prelude::{CanaryV0, MainnetV0, Network, TestnetV0};
 
+use std::net::SocketAddr;
+use std::path::PathBuf;
+use std::time::Duration;
 use structopt::StructOpt;
 use warp::Filter;
 
+/// Flags override the `ALEO_*` environment variables, which override the configuration file.
 #[derive(StructOpt, Debug)]
 struct Opt {
+    /// The TOML configuration file shared by the authorize and execute services.
+    #[structopt(short, long, parse(from_os_str))]
+    config: Option<PathBuf>,
+    /// Print the effective configuration and exit.
+    #[structopt(long)]
+    print_config: bool,
     #[structopt(short, long)]
-    network: String,
-    #[structopt(short, long, default_value = "8080")]
-    port: u16,
+    network: Option<String>,
+    #[structopt(short, long)]
+    port: Option<u16>,
+    /// Serve the gRPC interface on this port. Requires the `grpc` feature.
+    #[structopt(long)]
+    grpc_port: Option<u16>,
+    /// How long, in seconds, to wait for in-flight jobs after SIGINT or SIGTERM.
+    #[structopt(long)]
+    shutdown_timeout_secs: Option<u64>,
 }
 
-async fn run<N: Network>(port: u16) {
-    pretty_env_logger::init();
+impl Opt {
+    // Applies the flags that were given on top of the configuration.
+    fn apply(self, config: &mut Config<AuthorizeConfig>) {
+        let authorize = &mut config.service;
+        config.network = self.network.or(config.network.take());
+        authorize.port = self.port.unwrap_or(authorize.port);
+        authorize.grpc_port = self.grpc_port.or(authorize.grpc_port);
+        authorize.shutdown_timeout_secs = self
+            .shutdown_timeout_secs
+            .unwrap_or(authorize.shutdown_timeout_secs);
+    }
+}
+
+async fn run<N: Network>(config: AuthorizeConfig) {
+    // Load the process on every worker in the background.
+    // `/health/ready` reports ready once it is in memory.
+    tokio::task::spawn_blocking(|| {
+        if let Err(error) = warm_up_workers::<N>() {
+            log::error!("Failed to load the {} process: {error}", N::NAME);
+            std::process::exit(1);
+        }
+    });
+
+    // Serve the gRPC interface alongside the HTTP routes, if requested.
+    #[cfg(feature = "grpc")]
+    let grpc = match config.grpc_port {
+        Some(grpc_port) => match bind_grpc::<N>(SocketAddr::new(config.bind, grpc_port)).await {
+            Ok((_, server)) => Some(tokio::spawn(server)),
+            Err(error) => {
+                log::error!("Failed to serve the gRPC interface on port {grpc_port}: {error}");
+                std::process::exit(1);
+            }
+        },
+        None => None,
+    };
+    #[cfg(not(feature = "grpc"))]
+    if let Some(grpc_port) = config.grpc_port {
+        log::warn!("Ignoring --grpc-port {grpc_port}: built without the `grpc` feature");
+    }
 
     let routes = keygen_route::<N>()
         .or(authorize_route::<N>())
         .or(sign_route::<N>())
         .or(verify_route::<N>())
+        .or(health_route())
+        .or(live_route())
+        .or(ready_route::<N>())
+        .or(metrics_route(metrics()))
         .with(warp::trace(
             |info| tracing::debug_span!("Debugging headers", headers = ?info.request_headers()),
         ));
 
-    warp::serve(routes).run(([127, 0, 0, 1], port)).await;
+    // Stop on SIGINT or SIGTERM, once in-flight jobs finish or the timeout expires.
+    let address = SocketAddr::new(config.bind, config.port);
+    let shutdown_timeout = Duration::from_secs(config.shutdown_timeout_secs);
+    let (_, server) = warp::serve(routes.recover(handle_shutdown_rejection))
+        .bind_with_graceful_shutdown(address, drain(shutdown_timeout));
+    // Wait for the gRPC server to drain as well, within the same timeout.
+    #[cfg(feature = "grpc")]
+    let server = async move {
+        server.await;
+        if let Some(grpc) = grpc {
+            if let Ok(Err(error)) = grpc.await {
+                log::error!("The gRPC server failed: {error}");
+            }
+        }
+    };
+    serve_until_shutdown(server, shutdown_timeout).await;
 }
 
 #[tokio::main]
 async fn main() {
     let opt = Opt::from_args();
+    let print_config = opt.print_config;
+
+    // Resolve the configuration file, the environment and the flags, in that order.
+    let mut config = Config::<AuthorizeConfig>::load(opt.config.as_deref())
+        .expect("Failed to load the configuration");
+    opt.apply(&mut config);
+    // Print the configuration before validating it, to help find the invalid value.
+    if print_config {
+        print!(
+            "{}",
+            config
+                .to_toml()
+                .expect("Failed to render the configuration")
+        );
+        return;
+    }
+    if let Err(error) = config.validate() {
+        panic!("Invalid configuration: {error}");
+    }
 
-    match opt.network.as_str() {
-        "mainnet" => run::<MainnetV0>(opt.port).await,
-        "testnet" => run::<TestnetV0>(opt.port).await,
-        "canary" => run::<CanaryV0>(opt.port).await,
+    config.log_format.init();
+    if let Some(threads) = config.service.threads {
+        rayon::ThreadPoolBuilder::new()
+            .num_threads(threads)
+            .build_global()
+            .expect("Failed to build the worker thread pool");
+    }
+    set_settings(config.service.settings()).expect("Failed to initialize the settings");
+
+    match config.network.as_deref() {
+        Some("mainnet") => run::<MainnetV0>(config.service).await,
+        Some("testnet") => run::<TestnetV0>(config.service).await,
+        Some("canary") => run::<CanaryV0>(config.service).await,
         _ => panic!("Invalid network"),
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_flags_override_env_which_overrides_file() {
+        let path =
+            std::env::temp_dir().join(format!("authorize-config-{}.toml", std::process::id()));
+        let contents = r#"
+network = "testnet"
+
+[authorize]
+port = 9001
+body_limit = 1000
+threads = 2
+"#;
+        std::fs::write(&path, contents).unwrap();
+        std::env::set_var("ALEO_AUTHORIZE_PORT", "9002");
+        std::env::set_var("ALEO_AUTHORIZE_BODY_LIMIT", "2000");
+
+        let opt = Opt::from_iter([
+            "authorize-service",
+            "--config",
+            path.to_str().unwrap(),
+            "--port",
+            "9003",
+        ]);
+        let mut config = Config::<AuthorizeConfig>::load(opt.config.as_deref()).unwrap();
+        opt.apply(&mut config);
+        std::env::remove_var("ALEO_AUTHORIZE_PORT");
+        std::env::remove_var("ALEO_AUTHORIZE_BODY_LIMIT");
+        std::fs::remove_file(&path).unwrap();
+
+        assert_eq!(config.network.as_deref(), Some("testnet"));
+        assert_eq!(config.service.port, 9003);
+        assert_eq!(config.service.body_limit, 2000);
+        assert_eq!(config.service.threads, Some(2));
+    }
+}
@@ -0,0 +1,66 @@
+// Copyright (C) 2019-2024 Aleo Systems Inc.
+// This file is part of the Aleo SDK library.
+
+// The Aleo SDK library is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// The Aleo SDK library is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with the Aleo SDK library. If not, see <https://www.gnu.org/licenses/>.
+
+use super::*;
+
+use prometheus::{HistogramOpts, HistogramVec};
+use std::ops::Deref;
+use std::sync::OnceLock;
+
+// The histogram buckets, in seconds.
+const DURATION_BUCKETS: &[f64] = &[
+    0.001, 0.005, 0.01, 0.025, 0.05, 0.1, 0.25, 0.5, 1.0, 2.5, 5.0, 10.0, 30.0,
+];
+
+/// The Prometheus metrics exported by the authorize service.
+pub struct Metrics {
+    service: ServiceMetrics,
+    /// The time taken to load a process on a worker thread.
+    pub process_load_duration: HistogramVec,
+}
+
+impl Metrics {
+    fn new() -> Result<Self> {
+        let service = ServiceMetrics::new("authorize_service", DURATION_BUCKETS)?;
+        let process_load_duration = HistogramVec::new(
+            HistogramOpts::new(
+                "process_load_duration_seconds",
+                "The time taken to load a process",
+            )
+            .buckets(DURATION_BUCKETS.to_vec()),
+            &["network"],
+        )?;
+        service.register(&process_load_duration)?;
+        Ok(Self {
+            service,
+            process_load_duration,
+        })
+    }
+}
+
+impl Deref for Metrics {
+    type Target = ServiceMetrics;
+
+    fn deref(&self) -> &ServiceMetrics {
+        &self.service
+    }
+}
+
+/// Returns the metrics of the service.
+pub fn metrics() -> &'static Metrics {
+    static METRICS: OnceLock<Metrics> = OnceLock::new();
+    METRICS.get_or_init(|| Metrics::new().expect("Failed to register the metrics"))
+}
@@ -16,6 +16,8 @@
 
 use super::*;
 
+use std::time::Instant;
+
 pub enum ProcessVariant {
     MainnetV0(Process<MainnetV0>),
     TestnetV0(Process<TestnetV0>),
@@ -47,7 +49,9 @@ impl ProcessVariant {
         // Initialize the RNG.
         let rng = &mut rand_chacha::ChaCha20Rng::from_entropy();
 
-        // Authorize the function.
+        // Authorize the function. The phase is only recorded once the process has accepted
+        // the program and function, since they come straight from the request.
+        let start = Instant::now();
         let function_authorization = process.authorize::<A, _>(
             &request.private_key,
             request.program_id,
@@ -55,11 +59,18 @@ impl ProcessVariant {
             request.inputs.iter(),
             rng,
         )?;
+        metrics().observe_phase(
+            "authorize",
+            &request.program_id.to_string(),
+            &request.function_name.to_string(),
+            start.elapsed(),
+        );
 
         // Get the execution ID.
         let execution_id = function_authorization.to_execution_id()?;
 
         // Authorize the fee.
+        let start = Instant::now();
         let fee_authorization = process.authorize_fee_public::<A, _>(
             &request.private_key,
             *request.base_fee_in_microcredits,
@@ -67,6 +78,7 @@ impl ProcessVariant {
             execution_id,
             rng,
         )?;
+        metrics().observe_phase("authorize", "credits.aleo", "fee_public", start.elapsed());
 
         // Construct the response.
         let response = AuthorizeResponse::<N> {
@@ -39,3 +39,19 @@ pub struct SignResponse {
 pub struct VerifyResponse {
     pub result: bool,
 }
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct WorkerReadiness {
+    pub worker: usize,
+    pub process_loaded: bool,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct ReadinessResponse {
+    pub ready: bool,
+    pub draining: bool,
+    pub network: String,
+    pub version: String,
+    pub queue_depth: i64,
+    pub workers: Vec<WorkerReadiness>,
+}
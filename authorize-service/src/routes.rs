@@ -16,19 +16,57 @@
 
 use super::*;
 
-use warp::{Filter, Rejection, Reply};
+use warp::{http::StatusCode, Filter, Rejection, Reply};
+
+// GET /health
+pub fn health_route() -> impl Filter<Extract = impl Reply, Error = Rejection> + Clone {
+    warp::get()
+        .and(warp::path("health"))
+        .and(warp::path::end())
+        .map(|| match is_draining() {
+            true => warp::reply::with_status(
+                "Authorize Service is shutting down",
+                StatusCode::SERVICE_UNAVAILABLE,
+            ),
+            false => warp::reply::with_status("Authorize Service is up", StatusCode::OK),
+        })
+}
+
+// GET /health/live
+pub fn live_route() -> impl Filter<Extract = impl Reply, Error = Rejection> + Clone {
+    warp::get()
+        .and(warp::path!("health" / "live"))
+        .map(|| "Authorize Service is alive")
+}
+
+// GET /health/ready
+pub fn ready_route<N: Network>() -> impl Filter<Extract = impl Reply, Error = Rejection> + Clone {
+    warp::get().and(warp::path!("health" / "ready")).map(|| {
+        let readiness = readiness::<N>();
+        let status = match readiness.ready {
+            true => StatusCode::OK,
+            false => StatusCode::SERVICE_UNAVAILABLE,
+        };
+        warp::reply::with_status(warp::reply::json(&readiness), status)
+    })
+}
 
 // GET /keygen
 pub fn keygen_route<N: Network>() -> impl Filter<Extract = impl Reply, Error = Rejection> + Clone {
     warp::get()
+        .and(accepting_work())
         .and(warp::path("keygen"))
         .and(warp::path::param::<String>())
         .and(warp::path::end())
         .and_then(|seed: String| async move {
-            let response = match tokio_rayon::spawn_fifo(move || keygen::<N>(&seed)).await {
+            let response = match spawn_worker(metrics(), move || keygen::<N>(&seed)).await {
                 Ok(response) => response,
-                Err(_) => return Err(warp::reject()),
+                Err(_) => {
+                    metrics().record_request("keygen", "error");
+                    return Err(warp::reject());
+                }
             };
+            metrics().record_request("keygen", "success");
             Ok(warp::reply::json(&response))
         })
 }
@@ -37,15 +75,20 @@ pub fn keygen_route<N: Network>() -> impl Filter<Extract = impl Reply, Error = R
 pub fn authorize_route<N: Network>() -> impl Filter<Extract = impl Reply, Error = Rejection> + Clone
 {
     warp::post()
+        .and(accepting_work())
         .and(warp::path("authorize"))
         .and(warp::path::end())
-        .and(warp::body::content_length_limit(32 * 1024)) // 32 KiB
+        .and(warp::body::content_length_limit(settings().body_limit))
         .and(warp::body::bytes())
         .and_then(|bytes: Bytes| async move {
-            let response = match tokio_rayon::spawn_fifo(|| authorize::<N>(bytes)).await {
+            let response = match spawn_worker(metrics(), || authorize::<N>(bytes)).await {
                 Ok(response) => response,
-                Err(_) => return Err(warp::reject()),
+                Err(_) => {
+                    metrics().record_request("authorize", "error");
+                    return Err(warp::reject());
+                }
             };
+            metrics().record_request("authorize", "success");
             Ok(warp::reply::json(&response))
         })
 }
@@ -53,15 +96,20 @@ pub fn authorize_route<N: Network>() -> impl Filter<Extract = impl Reply, Error
 // POST /sign
 pub fn sign_route<N: Network>() -> impl Filter<Extract = impl Reply, Error = Rejection> + Clone {
     warp::post()
+        .and(accepting_work())
         .and(warp::path("sign"))
         .and(warp::path::end())
-        .and(warp::body::content_length_limit(32 * 1024)) // 32 KiB
+        .and(warp::body::content_length_limit(settings().body_limit))
         .and(warp::body::json())
         .and_then(|request: SignRequest<N>| async move {
-            let response = match tokio_rayon::spawn_fifo(|| sign::<N>(request)).await {
+            let response = match spawn_worker(metrics(), || sign::<N>(request)).await {
                 Ok(response) => response,
-                Err(_) => return Err(warp::reject()),
+                Err(_) => {
+                    metrics().record_request("sign", "error");
+                    return Err(warp::reject());
+                }
             };
+            metrics().record_request("sign", "success");
             Ok(warp::reply::json(&response))
         })
 }
@@ -69,15 +117,20 @@ pub fn sign_route<N: Network>() -> impl Filter<Extract = impl Reply, Error = Rej
 // POST /verify
 pub fn verify_route<N: Network>() -> impl Filter<Extract = impl Reply, Error = Rejection> + Clone {
     warp::post()
+        .and(accepting_work())
         .and(warp::path("verify"))
         .and(warp::path::end())
-        .and(warp::body::content_length_limit(32 * 1024)) // 32 KiB
+        .and(warp::body::content_length_limit(settings().body_limit))
         .and(warp::body::json())
         .and_then(|request: VerifyRequest<N>| async move {
-            let response = match tokio_rayon::spawn_fifo(|| verify::<N>(request)).await {
+            let response = match spawn_worker(metrics(), || verify::<N>(request)).await {
                 Ok(response) => response,
-                Err(_) => return Err(warp::reject()),
+                Err(_) => {
+                    metrics().record_request("verify", "error");
+                    return Err(warp::reject());
+                }
             };
+            metrics().record_request("verify", "success");
             Ok(warp::reply::json(&response))
         })
 }
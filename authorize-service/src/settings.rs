@@ -0,0 +1,48 @@
+// Copyright (C) 2019-2024 Aleo Systems Inc.
+// This file is part of the Aleo SDK library.
+
+// The Aleo SDK library is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// The Aleo SDK library is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with the Aleo SDK library. If not, see <https://www.gnu.org/licenses/>.
+
+use super::*;
+
+use std::sync::OnceLock;
+
+/// The settings shared by the routes of the authorize service.
+#[derive(Clone, Debug)]
+pub struct Settings {
+    /// The maximum request body size in bytes.
+    pub body_limit: u64,
+}
+
+impl Default for Settings {
+    fn default() -> Self {
+        Self {
+            body_limit: 32 * 1024, // 32 KiB
+        }
+    }
+}
+
+static SETTINGS: OnceLock<Settings> = OnceLock::new();
+
+/// Installs the service settings. This must be called before the routes are built.
+pub fn set_settings(settings: Settings) -> Result<()> {
+    SETTINGS
+        .set(settings)
+        .map_err(|_| anyhow!("The settings are already initialized"))
+}
+
+/// Returns the service settings, falling back to the defaults if none were installed.
+pub fn settings() -> &'static Settings {
+    SETTINGS.get_or_init(Settings::default)
+}
@@ -0,0 +1,227 @@
+// Copyright (C) 2019-2024 Aleo Systems Inc.
+// This file is part of the Aleo SDK library.
+
+// The Aleo SDK library is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// The Aleo SDK library is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with the Aleo SDK library. If not, see <https://www.gnu.org/licenses/>.
+
+use super::*;
+
+use snarkvm::prelude::{Authorization, FromBytes, Literal, Plaintext, ToBytes};
+use std::path::Path;
+
+impl ExpectedArgs {
+    /// Parses the expected execution ID, if one is given.
+    pub fn execution_id<N: Network>(&self) -> Result<Option<Field<N>>> {
+        self.execution_id
+            .as_deref()
+            .map(Field::from_str)
+            .transpose()
+            .map_err(|error| anyhow!("Invalid execution ID: {error}"))
+    }
+}
+
+/// Checks that the authorizations were signed for this network and that the fee pays for
+/// the function, returning the execution ID. With `expected`, the execution ID must match it.
+pub fn check_authorizations<N: Network>(
+    function_authorization: &Authorization<N>,
+    fee_authorization: &Authorization<N>,
+    expected: Option<&Field<N>>,
+) -> Result<Field<N>> {
+    // Ensure every request was signed for this network.
+    for authorization in [function_authorization, fee_authorization] {
+        for request in authorization.to_vec_deque() {
+            ensure!(
+                **request.network_id() == N::ID,
+                "The authorization of {}/{} is for network {}, not {} ({})",
+                request.program_id(),
+                request.function_name(),
+                **request.network_id(),
+                N::ID,
+                N::NAME
+            );
+        }
+    }
+
+    // Ensure the fee is bound to the function, through the execution ID in its last input.
+    let execution_id = function_authorization.to_execution_id()?;
+    let fee_request = fee_authorization.peek_next()?;
+    let fee_execution_id = match fee_request.inputs().last() {
+        Some(Value::Plaintext(Plaintext::Literal(Literal::Field(id), _))) => *id,
+        _ => bail!("The fee authorization does not carry an execution ID"),
+    };
+    ensure!(
+        fee_execution_id == execution_id,
+        "The fee pays for execution {fee_execution_id}, not {execution_id}"
+    );
+    check_expected(execution_id, expected)?;
+    Ok(execution_id)
+}
+
+/// Checks that the fee of the transaction pays for its execution, returning the execution ID.
+/// With `expected`, the execution ID must match it.
+pub fn check_transaction<N: Network>(
+    transaction: &Transaction<N>,
+    expected: Option<&Field<N>>,
+) -> Result<Field<N>> {
+    let Some(execution) = transaction.execution() else {
+        bail!("Transaction {} is not an execution", transaction.id());
+    };
+    let execution_id = execution.to_execution_id()?;
+    if let Some(fee) = transaction.fee_transition() {
+        let fee_execution_id = fee.deployment_or_execution_id()?;
+        ensure!(
+            fee_execution_id == execution_id,
+            "The fee pays for execution {fee_execution_id}, not {execution_id}"
+        );
+    }
+    check_expected(execution_id, expected)?;
+    Ok(execution_id)
+}
+
+/// Verifies the proofs of the transaction against this network's verifying keys, which
+/// rejects a transaction proved for another network.
+pub fn verify_proofs<N: Network>(transaction: &Transaction<N>) -> Result<()> {
+    let process = snarkvm::prelude::Process::<N>::load()?;
+    match (transaction.execution(), transaction.fee_transition()) {
+        (Some(execution), Some(fee)) => {
+            execute_service::verify_transaction(&process, execution, &fee)
+        }
+        (Some(execution), None) => execute_service::verify_execution(&process, execution),
+        (None, _) => bail!("Transaction {} is not an execution", transaction.id()),
+    }
+}
+
+/// Reads an `ExecuteRequest` written by `write_execute_request` and checks its authorizations.
+pub fn read_execute_request<N: Network>(
+    path: &Path,
+    expected: Option<&Field<N>>,
+) -> Result<(ExecuteRequest<N>, Field<N>)> {
+    let bytes = std::fs::read(path)
+        .map_err(|error| anyhow!("Failed to read {}: {error}", path.display()))?;
+    let request = ExecuteRequest::<N>::from_bytes_le(&bytes)
+        .map_err(|error| anyhow!("Failed to parse {}: {error}", path.display()))?;
+    let execution_id = check_authorizations(
+        &request.function_authorization,
+        &request.fee_authorization,
+        expected,
+    )?;
+    Ok((request, execution_id))
+}
+
+/// Writes the `ExecuteRequest` in the binary format accepted by `POST /execute`.
+pub fn write_execute_request<N: Network>(path: &Path, request: &ExecuteRequest<N>) -> Result<()> {
+    std::fs::write(path, request.to_bytes_le()?)
+        .map_err(|error| anyhow!("Failed to write {}: {error}", path.display()))
+}
+
+// Ensures the execution ID matches the one the operator expects, if any.
+fn check_expected<N: Network>(execution_id: Field<N>, expected: Option<&Field<N>>) -> Result<()> {
+    if let Some(expected) = expected {
+        ensure!(
+            execution_id == *expected,
+            "The artifact is for execution {execution_id}, not {expected}"
+        );
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use snarkvm::circuit::AleoTestnetV0;
+    use snarkvm::prelude::{Process, TestnetV0};
+
+    type CurrentNetwork = TestnetV0;
+
+    // Authorizes a public transfer and a fee for `execution_id`, or for the transfer itself.
+    fn sample_authorizations(
+        execution_id: Option<Field<CurrentNetwork>>,
+    ) -> (Authorization<CurrentNetwork>, Authorization<CurrentNetwork>) {
+        let process = Process::<CurrentNetwork>::load().unwrap();
+        let rng = &mut rand_chacha::ChaCha20Rng::seed_from_u64(1);
+        let private_key = PrivateKey::<CurrentNetwork>::new(rng).unwrap();
+        let inputs = [
+            Value::<CurrentNetwork>::from_str(
+                &Address::try_from(&private_key).unwrap().to_string(),
+            )
+            .unwrap(),
+            Value::from_str("100u64").unwrap(),
+        ];
+        let function_authorization = process
+            .authorize::<AleoTestnetV0, _>(
+                &private_key,
+                "credits.aleo",
+                "transfer_public",
+                inputs.iter(),
+                rng,
+            )
+            .unwrap();
+        let execution_id =
+            execution_id.unwrap_or_else(|| function_authorization.to_execution_id().unwrap());
+        let fee_authorization = process
+            .authorize_fee_public::<AleoTestnetV0, _>(&private_key, 100000, 10, execution_id, rng)
+            .unwrap();
+        (function_authorization, fee_authorization)
+    }
+
+    #[test]
+    fn test_check_authorizations() {
+        let (function_authorization, fee_authorization) = sample_authorizations(None);
+        let execution_id =
+            check_authorizations(&function_authorization, &fee_authorization, None).unwrap();
+        assert_eq!(
+            execution_id,
+            function_authorization.to_execution_id().unwrap()
+        );
+        check_authorizations(
+            &function_authorization,
+            &fee_authorization,
+            Some(&execution_id),
+        )
+        .unwrap();
+
+        // Reject an execution ID other than the expected one.
+        let other = Field::from_u64(1);
+        assert!(
+            check_authorizations(&function_authorization, &fee_authorization, Some(&other))
+                .is_err()
+        );
+
+        // Reject a fee that pays for another execution.
+        let (function_authorization, fee_authorization) = sample_authorizations(Some(other));
+        assert!(check_authorizations(&function_authorization, &fee_authorization, None).is_err());
+    }
+
+    #[test]
+    fn test_execute_request_round_trip() {
+        let (function_authorization, fee_authorization) = sample_authorizations(None);
+        let execution_id = function_authorization.to_execution_id().unwrap();
+        let request = ExecuteRequest::<CurrentNetwork> {
+            function_authorization,
+            fee_authorization,
+            state_root: None,
+            state_path: None,
+            options: Vec::new(),
+        };
+
+        let path = std::env::temp_dir().join(format!("request-test-{}.bin", std::process::id()));
+        write_execute_request(&path, &request).unwrap();
+        let result = read_execute_request::<CurrentNetwork>(&path, Some(&execution_id));
+        std::fs::remove_file(&path).unwrap();
+
+        let (read, read_execution_id) = result.unwrap();
+        assert_eq!(read_execution_id, execution_id);
+        assert_eq!(read.to_bytes_le().unwrap(), request.to_bytes_le().unwrap());
+    }
+}
@@ -0,0 +1,247 @@
+// Copyright (C) 2019-2024 Aleo Systems Inc.
+// This file is part of the Aleo SDK library.
+
+// The Aleo SDK library is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// The Aleo SDK library is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with the Aleo SDK library. If not, see <https://www.gnu.org/licenses/>.
+
+use super::*;
+
+use std::path::PathBuf;
+use structopt::StructOpt;
+
+/// A client for the authorize and execute services.
+/// Flags override the profile, which overrides the defaults.
+#[derive(StructOpt, Debug)]
+#[structopt(name = "transfer-client")]
+pub struct Opt {
+    /// The TOML profile holding the service URLs, network, key source and fees.
+    #[structopt(long, parse(from_os_str))]
+    pub profile: Option<PathBuf>,
+    /// The network: `mainnet`, `testnet` or `canary`.
+    #[structopt(short, long)]
+    pub network: Option<String>,
+    /// The base URL of the authorize service, e.g. `http://localhost:8080`.
+    #[structopt(long)]
+    pub authorize_url: Option<String>,
+    /// The base URL of an execute service replica, e.g. `http://localhost:8081`.
+    /// Repeat for each replica.
+    #[structopt(long = "execute-url")]
+    pub execute_urls: Vec<String>,
+    /// The base URL of the node, e.g. `http://localhost:3030`.
+    #[structopt(long)]
+    pub node_url: Option<String>,
+    /// The private key. Prefer `--private-key-file`, which keeps the key out of the shell history.
+    #[structopt(long)]
+    pub private_key: Option<String>,
+    /// A file holding the private key.
+    #[structopt(long, parse(from_os_str))]
+    pub private_key_file: Option<PathBuf>,
+    /// The encrypted wallet used when no private key is given. Defaults to `~/.aleo/wallet.json`.
+    #[structopt(long, parse(from_os_str))]
+    pub wallet: Option<PathBuf>,
+    /// The wallet account to use instead of the active one.
+    #[structopt(long)]
+    pub account: Option<String>,
+    /// The base fee in microcredits.
+    #[structopt(long)]
+    pub base_fee: Option<u64>,
+    /// The priority fee in microcredits.
+    #[structopt(long)]
+    pub priority_fee: Option<u64>,
+    /// Authorize and prove in this process instead of calling the services.
+    /// The node is still used for the state root, programs and broadcasts.
+    #[structopt(long)]
+    pub local: bool,
+    #[structopt(subcommand)]
+    pub command: Command,
+}
+
+#[derive(StructOpt, Debug)]
+pub enum Command {
+    /// Generate a private key through the authorize service, or locally with `--local` or
+    /// `--save`. The private key is only printed with `--show-private-key`.
+    Keygen {
+        /// The field element to derive the key from, e.g. `1234field`. A random one is used by default.
+        #[structopt(long)]
+        seed: Option<String>,
+        /// Save the key in the wallet under this account name.
+        #[structopt(long)]
+        save: Option<String>,
+        /// Print the private key.
+        #[structopt(long)]
+        show_private_key: bool,
+    },
+    /// Authorize a function call and its fee, writing the `AuthorizeResponse` as JSON.
+    Authorize {
+        #[structopt(flatten)]
+        call: CallArgs,
+        /// Write the output to this file instead of stdout.
+        #[structopt(short, long, parse(from_os_str))]
+        out: Option<PathBuf>,
+    },
+    /// Bind an authorization to a state root, writing the `ExecuteRequest` bytes accepted by
+    /// `POST /execute`. Run this on a host that can reach the node, or pass `--state-root`.
+    Prepare {
+        /// The `AuthorizeResponse` written by `authorize`.
+        #[structopt(long, parse(from_os_str))]
+        authorization: PathBuf,
+        /// The state root to prove against, instead of the latest one of the node.
+        #[structopt(long)]
+        state_root: Option<String>,
+        #[structopt(flatten)]
+        expected: ExpectedArgs,
+        /// The file receiving the request bytes.
+        #[structopt(short, long, parse(from_os_str))]
+        out: PathBuf,
+    },
+    /// Prove an authorization or a prepared request, writing the `Transaction` as JSON.
+    Execute {
+        /// The `AuthorizeResponse` written by `authorize`, proved against the latest state root.
+        #[structopt(long, parse(from_os_str), required_unless = "request")]
+        authorization: Option<PathBuf>,
+        /// The `ExecuteRequest` bytes written by `prepare`.
+        #[structopt(long, parse(from_os_str), conflicts_with = "authorization")]
+        request: Option<PathBuf>,
+        #[structopt(flatten)]
+        expected: ExpectedArgs,
+        /// Write the output to this file instead of stdout.
+        #[structopt(short, long, parse(from_os_str))]
+        out: Option<PathBuf>,
+    },
+    /// Broadcast a transaction to the node.
+    Broadcast {
+        /// The `Transaction` written by `execute`.
+        #[structopt(long, parse(from_os_str))]
+        transaction: PathBuf,
+        #[structopt(flatten)]
+        expected: ExpectedArgs,
+        /// Verify the proofs before broadcasting, which also rejects a transaction proved for
+        /// another network.
+        #[structopt(long)]
+        verify: bool,
+        #[structopt(flatten)]
+        wait: WaitArgs,
+    },
+    /// Transfer public credits: authorize, execute and broadcast in one step.
+    Transfer {
+        /// The address receiving the credits.
+        #[structopt(long)]
+        recipient: String,
+        /// The amount in microcredits.
+        #[structopt(long)]
+        amount: u64,
+        #[structopt(flatten)]
+        wait: WaitArgs,
+    },
+    /// Call any program function: authorize, execute and broadcast in one step.
+    Call {
+        #[structopt(flatten)]
+        call: CallArgs,
+        #[structopt(flatten)]
+        wait: WaitArgs,
+    },
+    /// Run authorize+execute pipelines against the services and report throughput and latency.
+    /// Transactions are not broadcast.
+    Loadgen {
+        #[structopt(flatten)]
+        call: CallArgs,
+        /// The number of pipelines in flight at once.
+        #[structopt(long, default_value = "4")]
+        concurrency: usize,
+        /// The number of pipelines started per second. By default they start as fast as
+        /// `--concurrency` allows.
+        #[structopt(long)]
+        rate: Option<f64>,
+        /// The number of pipelines to run.
+        #[structopt(long, default_value = "100")]
+        requests: usize,
+        /// Write the report as JSON to this file, or to stdout with `-`.
+        #[structopt(long, parse(from_os_str))]
+        json: Option<PathBuf>,
+    },
+    /// Manage the accounts of the encrypted wallet.
+    Wallet(WalletCommand),
+    /// Show the health of the services and the latest block height of the node.
+    Status,
+}
+
+/// The wallet commands. The password is read from `ALEO_WALLET_PASSWORD`, or prompted for.
+#[derive(StructOpt, Debug)]
+pub enum WalletCommand {
+    /// Generate a new account locally.
+    Create {
+        name: String,
+        /// The field element to derive the key from. A random one is used by default.
+        #[structopt(long)]
+        seed: Option<String>,
+    },
+    /// Import a private key, prompting for it unless `--file` is given.
+    Import {
+        name: String,
+        /// A file holding the private key.
+        #[structopt(long, parse(from_os_str))]
+        file: Option<PathBuf>,
+    },
+    /// Print the private key of an account, or of the active account.
+    Export {
+        name: Option<String>,
+        /// Write the private key to this file instead of stdout.
+        #[structopt(short, long, parse(from_os_str))]
+        out: Option<PathBuf>,
+    },
+    /// List the accounts and their addresses.
+    List,
+    /// Make an account the one used by default.
+    Use { name: String },
+}
+
+/// The function to call and its inputs.
+#[derive(StructOpt, Debug)]
+pub struct CallArgs {
+    #[structopt(long, default_value = "credits.aleo")]
+    pub program: String,
+    #[structopt(long)]
+    pub function: String,
+    /// An input of the function, as an Aleo literal. Repeat for each input.
+    #[structopt(long = "input")]
+    pub inputs: Vec<String>,
+    /// A JSON or YAML file holding the inputs as a list of Aleo literals, e.g. `["aleo1...", "5u64"]`.
+    /// Used instead of `--input`.
+    #[structopt(long, parse(from_os_str), conflicts_with = "inputs")]
+    pub inputs_file: Option<PathBuf>,
+    /// The source of the program, if it is not deployed on the node.
+    #[structopt(long, parse(from_os_str))]
+    pub program_file: Option<PathBuf>,
+}
+
+/// The checks of an artifact handed over from the previous stage.
+#[derive(StructOpt, Debug)]
+pub struct ExpectedArgs {
+    /// The execution ID the artifact must carry, as reported by the previous stage.
+    #[structopt(long)]
+    pub execution_id: Option<String>,
+}
+
+/// Whether and how long to wait for the broadcast transaction to be confirmed.
+#[derive(StructOpt, Debug)]
+pub struct WaitArgs {
+    /// Wait until the node confirms the transaction, then report its block and status.
+    #[structopt(long)]
+    pub wait: bool,
+    /// How long to wait for the confirmation, in seconds.
+    #[structopt(long, default_value = "120")]
+    pub wait_timeout_secs: u64,
+    /// How often to poll the node, in milliseconds.
+    #[structopt(long, default_value = "2000")]
+    pub poll_interval_ms: u64,
+}
@@ -0,0 +1,504 @@
+// Copyright (C) 2019-2024 Aleo Systems Inc.
+// This file is part of the Aleo SDK library.
+
+// The Aleo SDK library is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// The Aleo SDK library is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with the Aleo SDK library. If not, see <https://www.gnu.org/licenses/>.
+
+use super::*;
+
+use snarkvm::prelude::Program;
+use std::path::Path;
+use std::time::Duration;
+
+/// Runs the command against the services of the configured network.
+pub async fn run<N: Network>(context: &Context, command: Command) -> Result<()> {
+    match command {
+        Command::Keygen {
+            seed,
+            save,
+            show_private_key,
+        } => {
+            ensure!(
+                save.is_some() || show_private_key,
+                "Pass --save <name> to keep the key in the wallet, or --show-private-key to print it"
+            );
+            // A key kept in the wallet is derived locally, so that no service sees its seed.
+            let (private_key, address) = match save.is_some() {
+                true => local_keygen(parse_seed::<N>(seed)?).await?,
+                false => request_keygen::<N>(context, seed).await?,
+            };
+            if let Some(name) = save {
+                save_account(context, &name, &private_key)?;
+            }
+            if show_private_key {
+                println!("Private key: {private_key}");
+            }
+            println!("Address: {address}");
+        }
+        Command::Authorize { call, out } => {
+            let private_key = context.private_key::<N>()?;
+            let authorization = request_call(context, private_key, &call).await?;
+            let execution_id = check_authorizations(
+                &authorization.function_authorization,
+                &authorization.fee_authorization,
+                None,
+            )?;
+            write_json(out.as_deref(), &authorization)?;
+            report_stage::<N>("Authorized", execution_id);
+        }
+        Command::Prepare {
+            authorization,
+            state_root,
+            expected,
+            out,
+        } => {
+            let authorization = read_authorization::<N>(&authorization, &expected)?;
+            let state_root = state_root
+                .map(|root| {
+                    N::StateRoot::from_str(&root)
+                        .map_err(|_| anyhow!("Invalid state root '{root}'"))
+                })
+                .transpose()?;
+            let execute_request = prepare_execution(context, authorization, state_root).await?;
+            write_execute_request(&out, &execute_request)?;
+            let execution_id = execute_request.function_authorization.to_execution_id()?;
+            report_stage::<N>("Prepared", execution_id);
+        }
+        Command::Execute {
+            authorization,
+            request,
+            expected,
+            out,
+        } => {
+            let (execute_request, execution_id) = match (authorization, request) {
+                (_, Some(path)) => {
+                    read_execute_request::<N>(&path, expected.execution_id::<N>()?.as_ref())?
+                }
+                (Some(path), None) => {
+                    let authorization = read_authorization::<N>(&path, &expected)?;
+                    let execute_request = prepare_execution(context, authorization, None).await?;
+                    let execution_id = execute_request.function_authorization.to_execution_id()?;
+                    (execute_request, execution_id)
+                }
+                (None, None) => bail!("Pass --authorization or --request"),
+            };
+            let transaction = execute_prepared(context, execute_request)
+                .await?
+                .transaction;
+            check_transaction(&transaction, Some(&execution_id))?;
+            write_json(out.as_deref(), &transaction)?;
+            report_stage::<N>("Proved", execution_id);
+        }
+        Command::Broadcast {
+            transaction,
+            expected,
+            verify,
+            wait,
+        } => {
+            let transaction = read_json::<Transaction<N>>(&transaction)?;
+            check_transaction(&transaction, expected.execution_id::<N>()?.as_ref())?;
+            if verify {
+                let checked = transaction.clone();
+                tokio::task::spawn_blocking(move || verify_proofs(&checked)).await??;
+            }
+            submit(context, &transaction, None, &wait).await?;
+        }
+        Command::Transfer {
+            recipient,
+            amount,
+            wait,
+        } => {
+            let private_key = context.private_key::<N>()?;
+            let inputs = vec![
+                Value::from_str(&recipient)?,
+                Value::from_str(&format!("{amount}u64"))?,
+            ];
+            check_inputs(
+                &Program::credits()?,
+                &Identifier::from_str("transfer_public")?,
+                &inputs,
+            )?;
+            let authorization = request_authorization(
+                context,
+                private_key,
+                "credits.aleo",
+                "transfer_public",
+                inputs,
+            )
+            .await?;
+            let outcome = request_execution(context, authorization).await?;
+            submit(
+                context,
+                &outcome.transaction,
+                outcome.broadcast.as_ref(),
+                &wait,
+            )
+            .await?;
+        }
+        Command::Call { call, wait } => {
+            let private_key = context.private_key::<N>()?;
+            let authorization = request_call(context, private_key, &call).await?;
+            let outcome = request_execution(context, authorization).await?;
+            submit(
+                context,
+                &outcome.transaction,
+                outcome.broadcast.as_ref(),
+                &wait,
+            )
+            .await?;
+        }
+        Command::Loadgen {
+            call,
+            concurrency,
+            rate,
+            requests,
+            json,
+        } => {
+            ensure!(
+                !context.local,
+                "Load generation runs against the services, drop --local"
+            );
+            let inputs = load_call_inputs::<N>(context, &call).await?;
+            let plan = LoadPlan {
+                concurrency,
+                rate,
+                requests,
+            };
+            let report = run_load(context, plan, &call.program, &call.function, inputs).await?;
+            match json.as_deref() {
+                Some(path) if path == Path::new("-") => write_json(None, &report)?,
+                Some(path) => {
+                    write_json(Some(path), &report)?;
+                    print!("{}", report.table());
+                }
+                None => print!("{}", report.table()),
+            }
+        }
+        Command::Wallet(command) => wallet_command::<N>(context, command).await?,
+        Command::Status => status(context).await?,
+    }
+    Ok(())
+}
+
+/// Derives a private key and its address from `seed`, or from a random seed.
+pub async fn request_keygen<N: Network>(
+    context: &Context,
+    seed: Option<String>,
+) -> Result<(PrivateKey<N>, Address<N>)> {
+    let seed = parse_seed::<N>(seed)?;
+    match context.local {
+        true => local_keygen(seed).await,
+        false => Ok(context.service_client::<N>()?.keygen(&seed).await?),
+    }
+}
+
+/// Parses the seed, or generates a random one if none is given.
+pub fn parse_seed<N: Network>(seed: Option<String>) -> Result<Field<N>> {
+    match seed {
+        Some(seed) => Field::<N>::from_str(&seed),
+        None => Ok(Field::<N>::rand(
+            &mut rand_chacha::ChaCha20Rng::from_entropy(),
+        )),
+    }
+}
+
+/// Parses and checks the inputs of the call against the program, then authorizes it.
+pub async fn request_call<N: Network>(
+    context: &Context,
+    private_key: PrivateKey<N>,
+    call: &CallArgs,
+) -> Result<AuthorizeResponse<N>> {
+    let inputs = load_call_inputs::<N>(context, call).await?;
+    request_authorization(context, private_key, &call.program, &call.function, inputs).await
+}
+
+/// Runs a wallet command against the configured wallet file.
+pub async fn wallet_command<N: Network>(context: &Context, command: WalletCommand) -> Result<()> {
+    match command {
+        WalletCommand::Create { name, seed } => {
+            // The key is derived locally, so that no service sees its seed.
+            let (private_key, address) = local_keygen(parse_seed::<N>(seed)?).await?;
+            save_account(context, &name, &private_key)?;
+            println!("Address: {address}");
+        }
+        WalletCommand::Import { name, file } => {
+            let private_key = match file {
+                Some(path) => std::fs::read_to_string(&path)
+                    .map_err(|error| anyhow!("Failed to read {}: {error}", path.display()))?,
+                None => rpassword::prompt_password("Private key: ")?,
+            };
+            let private_key = PrivateKey::<N>::from_str(private_key.trim())?;
+            save_account(context, &name, &private_key)?;
+            println!("Address: {}", Address::try_from(&private_key)?);
+        }
+        WalletCommand::Export { name, out } => {
+            let wallet = Wallet::load(&context.wallet)?;
+            let private_key = wallet.private_key::<N>(name.as_deref(), &read_password(false)?)?;
+            match out {
+                Some(path) => write_private_file(&path, private_key.to_string().as_bytes())?,
+                None => println!("{private_key}"),
+            }
+        }
+        WalletCommand::List => {
+            let wallet = Wallet::load(&context.wallet)?;
+            for account in &wallet.accounts {
+                let marker = match wallet.active.as_deref() == Some(account.name.as_str()) {
+                    true => "*",
+                    false => " ",
+                };
+                println!("{marker} {:<20} {}", account.name, account.address);
+            }
+        }
+        WalletCommand::Use { name } => {
+            let mut wallet = Wallet::load(&context.wallet)?;
+            wallet.set_active(&name)?;
+            wallet.save(&context.wallet)?;
+            println!("Using account '{name}'");
+        }
+    }
+    Ok(())
+}
+
+/// Encrypts the private key into the wallet under `name`, creating the wallet if needed.
+pub fn save_account<N: Network>(
+    context: &Context,
+    name: &str,
+    private_key: &PrivateKey<N>,
+) -> Result<()> {
+    let (mut wallet, password) = match context.wallet.exists() {
+        true => (Wallet::load(&context.wallet)?, read_password(false)?),
+        false => (Wallet::new(), read_password(true)?),
+    };
+    wallet.add(name, private_key, &password)?;
+    wallet.save(&context.wallet)?;
+    eprintln!("Saved account '{name}' to {}", context.wallet.display());
+    Ok(())
+}
+
+/// Parses the inputs of the call and checks them against the program.
+pub async fn load_call_inputs<N: Network>(
+    context: &Context,
+    call: &CallArgs,
+) -> Result<Vec<Value<N>>> {
+    let program_id = ProgramID::<N>::from_str(&call.program)?;
+    let function_name = Identifier::<N>::from_str(&call.function)?;
+    let inputs = load_inputs::<N>(&call.inputs, call.inputs_file.as_deref())?;
+    let program = load_program(context, &program_id, call.program_file.as_deref()).await?;
+    check_inputs(&program, &function_name, &inputs)?;
+    Ok(inputs)
+}
+
+/// Authorizes `program/function` with `inputs`, along with a public fee.
+pub async fn request_authorization<N: Network>(
+    context: &Context,
+    private_key: PrivateKey<N>,
+    program: &str,
+    function: &str,
+    inputs: Vec<Value<N>>,
+) -> Result<AuthorizeResponse<N>> {
+    // Construct an `AuthorizeRequest`.
+    let authorize_request = AuthorizeRequest::<N> {
+        private_key,
+        program_id: ProgramID::from_str(program)?,
+        function_name: Identifier::from_str(function)?,
+        inputs,
+        base_fee_in_microcredits: U64::new(context.base_fee),
+        priority_fee_in_microcredits: U64::new(context.priority_fee),
+    };
+    match context.local {
+        true => local_authorize(authorize_request).await,
+        false => Ok(context
+            .service_client::<N>()?
+            .authorize(&authorize_request)
+            .await?),
+    }
+}
+
+/// Proves the authorization against the latest state root of the node.
+pub async fn request_execution<N: Network>(
+    context: &Context,
+    authorization: AuthorizeResponse<N>,
+) -> Result<ExecuteOutcome<N>> {
+    let execute_request = prepare_execution(context, authorization, None).await?;
+    execute_prepared(context, execute_request).await
+}
+
+/// Binds the authorization to `state_root`, or to the latest state root of the node.
+pub async fn prepare_execution<N: Network>(
+    context: &Context,
+    authorization: AuthorizeResponse<N>,
+    state_root: Option<N::StateRoot>,
+) -> Result<ExecuteRequest<N>> {
+    // Get the latest state root, if none is given.
+    let state_root = match state_root {
+        Some(state_root) => state_root,
+        None => context.service_client::<N>()?.state_root().await?,
+    };
+    eprintln!("Using state root: {state_root}");
+
+    // Construct an `ExecuteRequest`.
+    Ok(ExecuteRequest::<N> {
+        function_authorization: authorization.function_authorization,
+        fee_authorization: authorization.fee_authorization,
+        state_root: Some(state_root),
+        state_path: None,
+        options: Vec::new(),
+    })
+}
+
+/// Proves the request, in-process with `--local` or through the execute service. The outcome
+/// carries the node's answer if the service broadcast the transaction.
+pub async fn execute_prepared<N: Network>(
+    context: &Context,
+    execute_request: ExecuteRequest<N>,
+) -> Result<ExecuteOutcome<N>> {
+    match context.local {
+        true => Ok(ExecuteOutcome {
+            transaction: local_execute(execute_request).await?,
+            broadcast: None,
+        }),
+        false => Ok(context
+            .service_client::<N>()?
+            .execute(&execute_request)
+            .await?),
+    }
+}
+
+/// Sends the transaction to the node, returning the node's response body.
+pub async fn broadcast_transaction<N: Network>(
+    context: &Context,
+    transaction: &Transaction<N>,
+) -> Result<String> {
+    Ok(context
+        .service_client::<N>()?
+        .broadcast(transaction)
+        .await?)
+}
+
+/// Broadcasts the transaction, unless the node already accepted it from the execute service,
+/// and, if asked, waits for the node to confirm it.
+pub async fn submit<N: Network>(
+    context: &Context,
+    transaction: &Transaction<N>,
+    service_broadcast: Option<&ServiceBroadcast>,
+    wait: &WaitArgs,
+) -> Result<()> {
+    match service_broadcast {
+        Some(broadcast) if broadcast.is_accepted() => println!(
+            "The execute service broadcast transaction {}: {}",
+            transaction.id(),
+            broadcast.node_response
+        ),
+        _ => {
+            // Broadcast again if the node turned down the service's broadcast.
+            if let Some(broadcast) = service_broadcast {
+                eprintln!(
+                    "The node answered the execute service's broadcast with {}: {}",
+                    broadcast.node_status, broadcast.node_response
+                );
+            }
+            let body = broadcast_transaction(context, transaction).await?;
+            println!("Broadcast transaction {}: {body}", transaction.id());
+        }
+    }
+    if !wait.wait {
+        return Ok(());
+    }
+
+    let confirmation = context
+        .service_client::<N>()?
+        .wait_for_confirmation(
+            &transaction.id(),
+            Duration::from_secs(wait.wait_timeout_secs),
+            Duration::from_millis(wait.poll_interval_ms),
+        )
+        .await?;
+    let status = match confirmation.status {
+        ConfirmationStatus::Accepted => "accepted",
+        ConfirmationStatus::Rejected => "rejected",
+    };
+    println!(
+        "Transaction {} {status} in block {} ({})",
+        confirmation.transaction_id, confirmation.block_height, confirmation.block_hash
+    );
+    ensure!(
+        confirmation.status == ConfirmationStatus::Accepted,
+        "Transaction {} was rejected; only its fee was paid",
+        confirmation.transaction_id
+    );
+    Ok(())
+}
+
+/// Prints the health of both services, including every execute replica, and the latest block
+/// height of the node.
+pub async fn status(context: &Context) -> Result<()> {
+    let mut checks = vec![("authorize", format!("{}/health", context.authorize_url))];
+    for url in &context.execute_urls {
+        checks.push(("execute", format!("{url}/health/ready")));
+    }
+    checks.push(("node", context.node_endpoint("block/height/latest")));
+    for (name, url) in checks {
+        let line = match context.client.get(&url).send().await {
+            Ok(response) => {
+                let status = response.status();
+                format!("{status} {}", response.text().await.unwrap_or_default())
+            }
+            Err(error) => format!("unreachable ({error})"),
+        };
+        println!("{name:<10} {url}: {line}");
+    }
+    Ok(())
+}
+
+// Reads a JSON artifact written by an earlier command.
+fn read_json<T: DeserializeOwned>(path: &Path) -> Result<T> {
+    let contents = std::fs::read_to_string(path)
+        .map_err(|error| anyhow!("Failed to read {}: {error}", path.display()))?;
+    serde_json::from_str(&contents)
+        .map_err(|error| anyhow!("Failed to parse {}: {error}", path.display()))
+}
+
+// Reads an `AuthorizeResponse` and checks it against the network and the expected execution ID.
+fn read_authorization<N: Network>(
+    path: &Path,
+    expected: &ExpectedArgs,
+) -> Result<AuthorizeResponse<N>> {
+    let authorization = read_json::<AuthorizeResponse<N>>(path)?;
+    check_authorizations(
+        &authorization.function_authorization,
+        &authorization.fee_authorization,
+        expected.execution_id::<N>()?.as_ref(),
+    )?;
+    Ok(authorization)
+}
+
+// Reports the network and the execution ID, to be checked by the next stage.
+fn report_stage<N: Network>(stage: &str, execution_id: Field<N>) {
+    eprintln!(
+        "{stage} execution {execution_id} on {}; pass --execution-id {execution_id} to the next stage",
+        N::NAME
+    );
+}
+
+// Writes a JSON artifact to the file, or to stdout if none is given.
+fn write_json<T: Serialize>(path: Option<&Path>, value: &T) -> Result<()> {
+    let json = serde_json::to_string_pretty(value)?;
+    match path {
+        Some(path) => std::fs::write(path, json)
+            .map_err(|error| anyhow!("Failed to write {}: {error}", path.display())),
+        None => {
+            println!("{json}");
+            Ok(())
+        }
+    }
+}
@@ -0,0 +1,245 @@
+// Copyright (C) 2019-2024 Aleo Systems Inc.
+// This file is part of the Aleo SDK library.
+
+// The Aleo SDK library is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// The Aleo SDK library is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with the Aleo SDK library. If not, see <https://www.gnu.org/licenses/>.
+
+use super::*;
+
+use snarkvm::prelude::{Plaintext, PlaintextType, Program, ValueType};
+use std::path::Path;
+
+/// Parses the inputs, given either as literals or as a JSON or YAML list in a file.
+pub fn load_inputs<N: Network>(literals: &[String], file: Option<&Path>) -> Result<Vec<Value<N>>> {
+    let literals = match file {
+        Some(path) => {
+            let contents = std::fs::read_to_string(path)
+                .map_err(|error| anyhow!("Failed to read {}: {error}", path.display()))?;
+            let is_yaml = matches!(
+                path.extension().and_then(|extension| extension.to_str()),
+                Some("yaml" | "yml")
+            );
+            let literals: Vec<String> = match is_yaml {
+                true => serde_yaml::from_str(&contents)?,
+                false => serde_json::from_str(&contents)?,
+            };
+            literals
+        }
+        None => literals.to_vec(),
+    };
+    literals
+        .iter()
+        .enumerate()
+        .map(|(index, literal)| {
+            Value::from_str(literal)
+                .map_err(|error| anyhow!("Invalid input {index} '{literal}': {error}"))
+        })
+        .collect()
+}
+
+/// Returns the program from its source file, or from the node if no file is given.
+pub async fn load_program<N: Network>(
+    context: &Context,
+    program_id: &ProgramID<N>,
+    file: Option<&Path>,
+) -> Result<Program<N>> {
+    match file {
+        Some(path) => {
+            let source = std::fs::read_to_string(path)
+                .map_err(|error| anyhow!("Failed to read {}: {error}", path.display()))?;
+            let program = Program::<N>::from_str(&source)?;
+            ensure!(
+                program.id() == program_id,
+                "{} holds {}, not {program_id}",
+                path.display(),
+                program.id()
+            );
+            Ok(program)
+        }
+        None if program_id.to_string() == "credits.aleo" => Program::credits(),
+        None => Ok(context.service_client::<N>()?.program(program_id).await?),
+    }
+}
+
+/// Checks the inputs against the signature of the function, so that mistakes fail before
+/// the authorize call. Record inputs are only checked for their kind.
+pub fn check_inputs<N: Network>(
+    program: &Program<N>,
+    function_name: &Identifier<N>,
+    inputs: &[Value<N>],
+) -> Result<()> {
+    let function = program.get_function(function_name)?;
+    let expected = function.inputs();
+    ensure!(
+        inputs.len() == expected.len(),
+        "{}/{function_name} takes {} inputs, but {} were given",
+        program.id(),
+        expected.len(),
+        inputs.len()
+    );
+    for (index, (input, expected)) in inputs.iter().zip(expected.iter()).enumerate() {
+        check_value(program, input, expected.value_type())
+            .map_err(|error| anyhow!("Input {index} of {function_name}: {error}"))?;
+    }
+    Ok(())
+}
+
+// Checks that the value has the given type.
+fn check_value<N: Network>(
+    program: &Program<N>,
+    value: &Value<N>,
+    value_type: &ValueType<N>,
+) -> Result<()> {
+    match (value_type, value) {
+        (
+            ValueType::Constant(plaintext_type)
+            | ValueType::Public(plaintext_type)
+            | ValueType::Private(plaintext_type),
+            Value::Plaintext(plaintext),
+        ) => check_plaintext(program, plaintext, plaintext_type),
+        (ValueType::Record(_) | ValueType::ExternalRecord(_), Value::Record(_)) => Ok(()),
+        (value_type, _) => bail!("expected {value_type}, found '{value}'"),
+    }
+}
+
+// Checks that the plaintext has the given type, including the members of structs.
+fn check_plaintext<N: Network>(
+    program: &Program<N>,
+    plaintext: &Plaintext<N>,
+    plaintext_type: &PlaintextType<N>,
+) -> Result<()> {
+    match (plaintext_type, plaintext) {
+        (PlaintextType::Literal(literal_type), Plaintext::Literal(literal, _)) => {
+            ensure!(
+                literal.to_type() == *literal_type,
+                "expected {literal_type}, found '{literal}'"
+            );
+            Ok(())
+        }
+        (PlaintextType::Struct(struct_name), Plaintext::Struct(members, _)) => {
+            let expected = program.get_struct(struct_name)?.members();
+            ensure!(
+                members.len() == expected.len(),
+                "expected {struct_name} with {} members, found {}",
+                expected.len(),
+                members.len()
+            );
+            // The members must be given in the order they are declared.
+            for ((name, member), (expected_name, member_type)) in members.iter().zip(expected) {
+                ensure!(
+                    name == expected_name,
+                    "expected member '{expected_name}' of {struct_name}, found '{name}'"
+                );
+                check_plaintext(program, member, member_type)
+                    .map_err(|error| anyhow!("member '{name}' of {struct_name}: {error}"))?;
+            }
+            Ok(())
+        }
+        (PlaintextType::Array(array_type), Plaintext::Array(elements, _)) => {
+            ensure!(
+                elements.len() == **array_type.length() as usize,
+                "expected {plaintext_type}, found {} elements",
+                elements.len()
+            );
+            elements.iter().try_for_each(|element| {
+                check_plaintext(program, element, array_type.next_element_type())
+            })
+        }
+        (plaintext_type, _) => bail!("expected {plaintext_type}, found '{plaintext}'"),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use snarkvm::prelude::TestnetV0;
+
+    type CurrentNetwork = TestnetV0;
+
+    const PROGRAM: &str = r"
+program example.aleo;
+
+struct point:
+    x as u32;
+    y as u32;
+
+function main:
+    input r0 as address.public;
+    input r1 as u64.private;
+    input r2 as [u8; 2u32].public;
+    input r3 as point.private;
+";
+
+    fn check(inputs: &[&str]) -> Result<()> {
+        let program = Program::<CurrentNetwork>::from_str(PROGRAM).unwrap();
+        let inputs = inputs
+            .iter()
+            .map(|input| Value::from_str(input))
+            .collect::<Result<Vec<_>>>()?;
+        check_inputs(&program, &Identifier::from_str("main").unwrap(), &inputs)
+    }
+
+    const ADDRESS: &str = "aleo1zcsyu7wfrdp4n6gq752p3np45sat9d6zun2uhjer2h4skccsgsgq7ndrnj";
+
+    #[test]
+    fn test_accepts_matching_inputs() {
+        check(&[ADDRESS, "5u64", "[1u8, 2u8]", "{ x: 1u32, y: 2u32 }"]).unwrap();
+    }
+
+    #[test]
+    fn test_rejects_wrong_literal_type() {
+        assert!(check(&[ADDRESS, "5u32", "[1u8, 2u8]", "{ x: 1u32, y: 2u32 }"]).is_err());
+    }
+
+    #[test]
+    fn test_rejects_wrong_array_length() {
+        assert!(check(&[ADDRESS, "5u64", "[1u8]", "{ x: 1u32, y: 2u32 }"]).is_err());
+    }
+
+    #[test]
+    fn test_rejects_misspelled_struct_member() {
+        let error = check(&[ADDRESS, "5u64", "[1u8, 2u8]", "{ x: 1u32, z: 2u32 }"]).unwrap_err();
+        assert!(error.to_string().contains("expected member 'y' of point"));
+    }
+
+    #[test]
+    fn test_rejects_mistyped_struct_member() {
+        let error = check(&[ADDRESS, "5u64", "[1u8, 2u8]", "{ x: 1u32, y: 2u64 }"]).unwrap_err();
+        assert!(error.to_string().contains("member 'y' of point"));
+    }
+
+    #[test]
+    fn test_rejects_missing_struct_member() {
+        assert!(check(&[ADDRESS, "5u64", "[1u8, 2u8]", "{ x: 1u32 }"]).is_err());
+    }
+
+    #[test]
+    fn test_rejects_wrong_input_count() {
+        assert!(check(&[ADDRESS, "5u64"]).is_err());
+    }
+
+    #[test]
+    fn test_reads_yaml_and_json_inputs() {
+        let directory = std::env::temp_dir();
+        let json = directory.join("transfer-client-inputs.json");
+        let yaml = directory.join("transfer-client-inputs.yaml");
+        std::fs::write(&json, format!("[\"{ADDRESS}\", \"5u64\"]")).unwrap();
+        std::fs::write(&yaml, format!("- {ADDRESS}\n- 5u64\n")).unwrap();
+        for path in [json, yaml] {
+            let inputs = load_inputs::<CurrentNetwork>(&[], Some(&path)).unwrap();
+            assert_eq!(inputs.len(), 2);
+            assert_eq!(inputs[1], Value::from_str("5u64").unwrap());
+        }
+    }
+}
@@ -0,0 +1,57 @@
+// Copyright (C) 2019-2024 Aleo Systems Inc.
+// This file is part of the Aleo SDK library.
+
+// The Aleo SDK library is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// The Aleo SDK library is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with the Aleo SDK library. If not, see <https://www.gnu.org/licenses/>.
+
+pub mod artifacts;
+pub use artifacts::*;
+
+pub mod cli;
+pub use cli::*;
+
+pub mod commands;
+pub use commands::*;
+
+pub mod inputs;
+pub use inputs::*;
+
+pub mod loadgen;
+pub use loadgen::*;
+
+pub mod local;
+pub use local::*;
+
+pub mod profile;
+pub use profile::*;
+
+pub mod wallet;
+pub use wallet::*;
+
+use snarkvm::ledger::block::Transaction;
+use snarkvm::prelude::{
+    Address, Deserialize, Field, Identifier, Network, PrivateKey, ProgramID, Serialize, Uniform,
+    Value, U64,
+};
+
+use service_client::{
+    AuthorizeRequest, AuthorizeResponse, ClientConfig, ConfirmationStatus, ExecuteOutcome,
+    ExecuteRequest, ServiceBroadcast, ServiceClient,
+};
+
+use anyhow::{anyhow, bail, ensure, Result};
+use rand_chacha::rand_core::SeedableRng;
+use reqwest::Client;
+use serde::de::DeserializeOwned;
+use std::str::FromStr;
+use std::time::Duration;
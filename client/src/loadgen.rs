@@ -0,0 +1,364 @@
+// Copyright (C) 2019-2024 Aleo Systems Inc.
+// This file is part of the Aleo SDK library.
+
+// The Aleo SDK library is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// The Aleo SDK library is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with the Aleo SDK library. If not, see <https://www.gnu.org/licenses/>.
+
+use super::*;
+
+use service_client::ServiceError;
+use std::collections::BTreeMap;
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+use tokio::sync::Semaphore;
+use tokio::task::JoinSet;
+
+/// The shape of a load run.
+#[derive(Clone, Copy, Debug)]
+pub struct LoadPlan {
+    /// The number of pipelines in flight at once.
+    pub concurrency: usize,
+    /// The number of pipelines started per second, or as fast as `concurrency` allows.
+    pub rate: Option<f64>,
+    /// The number of pipelines to run.
+    pub requests: usize,
+}
+
+/// The outcome of one authorize+execute pipeline.
+#[derive(Clone, Debug)]
+pub struct Sample {
+    /// The duration of each phase that completed, in order.
+    pub phases: Vec<(&'static str, Duration)>,
+    /// The phase that failed and why, if one did.
+    pub error: Option<String>,
+}
+
+/// The latency distribution of a phase, in milliseconds.
+#[derive(Clone, Debug, PartialEq, Serialize)]
+pub struct LatencySummary {
+    pub count: usize,
+    pub p50_ms: f64,
+    pub p95_ms: f64,
+    pub p99_ms: f64,
+    pub max_ms: f64,
+}
+
+/// The result of a load run.
+#[derive(Clone, Debug, Serialize)]
+pub struct LoadReport {
+    pub requests: usize,
+    pub succeeded: usize,
+    pub failed: usize,
+    pub concurrency: usize,
+    pub target_rate: Option<f64>,
+    pub elapsed_secs: f64,
+    /// Successful pipelines per second.
+    pub throughput: f64,
+    /// The latency of each phase, the wait for a free slot as `queued`, and of the whole pipeline
+    /// from its scheduled start as `total`.
+    pub latency: BTreeMap<String, LatencySummary>,
+    /// The number of failures by phase and error.
+    pub errors: BTreeMap<String, usize>,
+}
+
+/// Runs `plan.requests` pipelines calling `program/function` with `inputs`.
+pub async fn run_load<N: Network>(
+    context: &Context,
+    plan: LoadPlan,
+    program: &str,
+    function: &str,
+    inputs: Vec<Value<N>>,
+) -> Result<LoadReport> {
+    ensure!(plan.concurrency > 0, "The concurrency must be at least 1");
+    if let Some(rate) = plan.rate {
+        ensure!(rate > 0.0, "The rate must be positive");
+    }
+    let client = Arc::new(load_client::<N>(context)?);
+    let request = Arc::new(AuthorizeRequest::<N> {
+        private_key: context.private_key::<N>()?,
+        program_id: ProgramID::from_str(program)?,
+        function_name: Identifier::from_str(function)?,
+        inputs,
+        base_fee_in_microcredits: U64::new(context.base_fee),
+        priority_fee_in_microcredits: U64::new(context.priority_fee),
+    });
+
+    // Start the pipelines at the target rate, with at most `concurrency` in flight. The latency
+    // of a pipeline counts from its scheduled start, so waiting for a permit is included.
+    let permits = Arc::new(Semaphore::new(plan.concurrency));
+    let mut pipelines = JoinSet::new();
+    let start = Instant::now();
+    for index in 0..plan.requests {
+        let scheduled = scheduled_start(start, plan.rate, index);
+        if let Some(scheduled) = scheduled {
+            tokio::time::sleep_until(scheduled.into()).await;
+        }
+        let permit = permits.clone().acquire_owned().await?;
+        let scheduled = scheduled.unwrap_or_else(Instant::now);
+        let (client, request) = (client.clone(), request.clone());
+        pipelines.spawn(async move {
+            let sample = pipeline(&client, &request, scheduled).await;
+            drop(permit);
+            sample
+        });
+    }
+
+    let mut samples = Vec::with_capacity(plan.requests);
+    while let Some(sample) = pipelines.join_next().await {
+        samples.push(sample?);
+    }
+    Ok(LoadReport::new(plan, start.elapsed(), &samples))
+}
+
+// Returns a client that makes a single attempt per request, so that every failure is reported
+// and no retry is counted as latency.
+fn load_client<N: Network>(context: &Context) -> Result<ServiceClient<N>> {
+    Ok(ServiceClient::new(ClientConfig {
+        max_attempts: 1,
+        ..context.client_config()
+    })?)
+}
+
+// Returns when the `index`-th pipeline is due at `rate`, or `None` if pipelines start as soon
+// as a permit is free.
+fn scheduled_start(start: Instant, rate: Option<f64>, index: usize) -> Option<Instant> {
+    rate.map(|rate| start + Duration::from_secs_f64(index as f64 / rate))
+}
+
+// Authorizes and executes the request, timing each phase and the whole pipeline from `scheduled`.
+async fn pipeline<N: Network>(
+    client: &ServiceClient<N>,
+    request: &AuthorizeRequest<N>,
+    scheduled: Instant,
+) -> Sample {
+    let mut sample = Sample {
+        phases: vec![("queued", scheduled.elapsed())],
+        error: None,
+    };
+
+    // Authorize the function and the fee.
+    let phase = Instant::now();
+    let authorization = match client.authorize(request).await {
+        Ok(authorization) => authorization,
+        Err(error) => return sample.failed("authorize", &error),
+    };
+    sample.phases.push(("authorize", phase.elapsed()));
+
+    // Get the latest state root.
+    let phase = Instant::now();
+    let state_root = match client.state_root().await {
+        Ok(state_root) => state_root,
+        Err(error) => return sample.failed("state_root", &error),
+    };
+    sample.phases.push(("state_root", phase.elapsed()));
+
+    // Prove the transaction.
+    let phase = Instant::now();
+    let execute_request = ExecuteRequest::<N> {
+        function_authorization: authorization.function_authorization,
+        fee_authorization: authorization.fee_authorization,
+        state_root: Some(state_root),
+        state_path: None,
+        options: Vec::new(),
+    };
+    if let Err(error) = client.execute(&execute_request).await {
+        return sample.failed("execute", &error);
+    }
+    sample.phases.push(("execute", phase.elapsed()));
+
+    sample.phases.push(("total", scheduled.elapsed()));
+    sample
+}
+
+impl Sample {
+    // Records that `phase` failed with `error`.
+    fn failed(mut self, phase: &str, error: &ServiceError) -> Self {
+        let kind = match error {
+            ServiceError::Service { status, code, .. } => format!("{status} {code:?}"),
+            ServiceError::Timeout => "timeout".to_string(),
+            ServiceError::Transport(_) => "transport".to_string(),
+            ServiceError::Decode(_) => "decode".to_string(),
+            error => error.to_string(),
+        };
+        self.error = Some(format!("{phase}: {kind}"));
+        self
+    }
+}
+
+impl LoadReport {
+    /// Aggregates the samples of a run that took `elapsed`.
+    pub fn new(plan: LoadPlan, elapsed: Duration, samples: &[Sample]) -> Self {
+        let mut durations = BTreeMap::<String, Vec<Duration>>::new();
+        let mut errors = BTreeMap::new();
+        for sample in samples {
+            for (phase, duration) in &sample.phases {
+                durations
+                    .entry(phase.to_string())
+                    .or_default()
+                    .push(*duration);
+            }
+            if let Some(error) = &sample.error {
+                *errors.entry(error.clone()).or_insert(0) += 1;
+            }
+        }
+        let failed = samples
+            .iter()
+            .filter(|sample| sample.error.is_some())
+            .count();
+        let succeeded = samples.len() - failed;
+        Self {
+            requests: samples.len(),
+            succeeded,
+            failed,
+            concurrency: plan.concurrency,
+            target_rate: plan.rate,
+            elapsed_secs: elapsed.as_secs_f64(),
+            throughput: match elapsed.is_zero() {
+                true => 0.0,
+                false => succeeded as f64 / elapsed.as_secs_f64(),
+            },
+            latency: durations
+                .into_iter()
+                .map(|(phase, durations)| (phase, LatencySummary::new(durations)))
+                .collect(),
+            errors,
+        }
+    }
+
+    /// Renders the report as a plain-text table.
+    pub fn table(&self) -> String {
+        let mut table = format!(
+            "{} requests, {} succeeded, {} failed in {:.1}s ({:.2} tx/s)\n\n",
+            self.requests, self.succeeded, self.failed, self.elapsed_secs, self.throughput
+        );
+        table.push_str(&format!(
+            "{:<12} {:>7} {:>10} {:>10} {:>10} {:>10}\n",
+            "phase", "count", "p50 ms", "p95 ms", "p99 ms", "max ms"
+        ));
+        for (phase, summary) in &self.latency {
+            table.push_str(&format!(
+                "{:<12} {:>7} {:>10.1} {:>10.1} {:>10.1} {:>10.1}\n",
+                phase,
+                summary.count,
+                summary.p50_ms,
+                summary.p95_ms,
+                summary.p99_ms,
+                summary.max_ms
+            ));
+        }
+        if !self.errors.is_empty() {
+            table.push_str(&format!("\n{:<40} {:>7}\n", "error", "count"));
+            for (error, count) in &self.errors {
+                table.push_str(&format!("{error:<40} {count:>7}\n"));
+            }
+        }
+        table
+    }
+}
+
+impl LatencySummary {
+    /// Summarizes a non-empty set of durations.
+    fn new(mut durations: Vec<Duration>) -> Self {
+        durations.sort();
+        let millis = |duration: Duration| duration.as_secs_f64() * 1000.0;
+        Self {
+            count: durations.len(),
+            p50_ms: millis(percentile(&durations, 50.0)),
+            p95_ms: millis(percentile(&durations, 95.0)),
+            p99_ms: millis(percentile(&durations, 99.0)),
+            max_ms: millis(durations[durations.len() - 1]),
+        }
+    }
+}
+
+// Returns the nearest-rank percentile of the sorted, non-empty `durations`.
+fn percentile(durations: &[Duration], percentile: f64) -> Duration {
+    let rank = (percentile / 100.0 * durations.len() as f64).ceil() as usize;
+    durations[rank.clamp(1, durations.len()) - 1]
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use snarkvm::prelude::TestnetV0;
+    use structopt::StructOpt;
+
+    fn millis(millis: u64) -> Duration {
+        Duration::from_millis(millis)
+    }
+
+    #[test]
+    fn test_percentile() {
+        let durations = (1..=100).map(millis).collect::<Vec<_>>();
+        assert_eq!(percentile(&durations, 50.0), millis(50));
+        assert_eq!(percentile(&durations, 95.0), millis(95));
+        assert_eq!(percentile(&durations, 99.0), millis(99));
+        assert_eq!(percentile(&[millis(7)], 99.0), millis(7));
+    }
+
+    #[test]
+    fn test_scheduled_start() {
+        let start = Instant::now();
+        assert_eq!(scheduled_start(start, None, 3), None);
+        assert_eq!(scheduled_start(start, Some(4.0), 0), Some(start));
+        assert_eq!(
+            scheduled_start(start, Some(4.0), 3),
+            Some(start + millis(750))
+        );
+    }
+
+    #[test]
+    fn test_load_client_does_not_retry() {
+        let opt = Opt::from_iter(["transfer-client", "status"]);
+        let context = Context::new(&opt, Profile::default()).unwrap();
+        let client = load_client::<TestnetV0>(&context).unwrap();
+        assert_eq!(client.config().max_attempts, 1);
+        assert_eq!(client.config().execute_urls, context.execute_urls);
+    }
+
+    #[test]
+    fn test_report_aggregates_samples() {
+        let succeeded = Sample {
+            phases: vec![
+                ("authorize", millis(10)),
+                ("execute", millis(100)),
+                ("total", millis(110)),
+            ],
+            error: None,
+        };
+        let failed = Sample {
+            phases: vec![("authorize", millis(20))],
+            error: Some("execute: 500 ExecutionFailed".to_string()),
+        };
+        let plan = LoadPlan {
+            concurrency: 2,
+            rate: None,
+            requests: 3,
+        };
+        let report = LoadReport::new(
+            plan,
+            Duration::from_secs(2),
+            &[succeeded.clone(), succeeded, failed],
+        );
+        assert_eq!(report.requests, 3);
+        assert_eq!(report.succeeded, 2);
+        assert_eq!(report.failed, 1);
+        assert_eq!(report.throughput, 1.0);
+        assert_eq!(report.latency["authorize"].count, 3);
+        assert_eq!(report.latency["authorize"].max_ms, 20.0);
+        assert_eq!(report.latency["total"].count, 2);
+        assert_eq!(report.errors["execute: 500 ExecutionFailed"], 1);
+        assert!(report.table().contains("execute: 500 ExecutionFailed"));
+    }
+}
@@ -0,0 +1,117 @@
+// Copyright (C) 2019-2024 Aleo Systems Inc.
+// This file is part of the Aleo SDK library.
+
+// The Aleo SDK library is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// The Aleo SDK library is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with the Aleo SDK library. If not, see <https://www.gnu.org/licenses/>.
+
+use super::*;
+
+use snarkvm::prelude::{FromBytes, ToBytes};
+use warp::hyper::body::Bytes;
+
+// Runs the proving work of `f` off the async runtime, as the services do on their workers.
+async fn spawn_local<T: Send + 'static>(
+    f: impl FnOnce() -> Result<T> + Send + 'static,
+) -> Result<T> {
+    tokio::task::spawn_blocking(f).await?
+}
+
+/// Derives a private key and its address in-process, as `GET /keygen/{seed}` would.
+pub async fn local_keygen<N: Network>(seed: Field<N>) -> Result<(PrivateKey<N>, Address<N>)> {
+    spawn_local(move || {
+        let response = authorize_service::keygen::<N>(&(*seed).to_string())?;
+        Ok((
+            PrivateKey::from_bytes_le(&response.private_key)?,
+            Address::from_bytes_le(&response.address)?,
+        ))
+    })
+    .await
+}
+
+/// Authorizes a function call and its fee in-process, as `POST /authorize` would.
+pub async fn local_authorize<N: Network>(
+    request: AuthorizeRequest<N>,
+) -> Result<AuthorizeResponse<N>> {
+    let bytes = Bytes::from(serde_json::to_vec(&request)?);
+    spawn_local(move || {
+        let response = authorize_service::authorize::<N>(bytes)?;
+        Ok(serde_json::from_value(response)?)
+    })
+    .await
+}
+
+/// Proves an execution request in-process, as `POST /execute` would.
+pub async fn local_execute<N: Network>(request: ExecuteRequest<N>) -> Result<Transaction<N>> {
+    let bytes = Bytes::from(request.to_bytes_le()?);
+    spawn_local(move || {
+        let output = execute_service::execute::<N>(bytes)?;
+        Transaction::from_bytes_le(&output.transaction)
+    })
+    .await
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use snarkvm::prelude::{Process, TestnetV0};
+
+    type CurrentNetwork = TestnetV0;
+
+    #[tokio::test(flavor = "multi_thread")]
+    async fn test_local_authorize_and_execute() {
+        let (private_key, address) = local_keygen::<CurrentNetwork>(Field::from_u64(1234))
+            .await
+            .unwrap();
+
+        // Authorize a public transfer to the same account.
+        let authorization = local_authorize(AuthorizeRequest {
+            private_key,
+            program_id: ProgramID::from_str("credits.aleo").unwrap(),
+            function_name: Identifier::from_str("transfer_public").unwrap(),
+            inputs: vec![
+                Value::from_str(&address.to_string()).unwrap(),
+                Value::from_str("100u64").unwrap(),
+            ],
+            base_fee_in_microcredits: U64::new(300_000),
+            priority_fee_in_microcredits: U64::new(10),
+        })
+        .await
+        .unwrap();
+        let execution_id = authorization
+            .function_authorization
+            .to_execution_id()
+            .unwrap();
+
+        // Prove it against a fixed state root.
+        let state_root = <CurrentNetwork as Network>::StateRoot::from(Field::from_u64(7));
+        let transaction = local_execute(ExecuteRequest {
+            function_authorization: authorization.function_authorization,
+            fee_authorization: authorization.fee_authorization,
+            state_root: Some(state_root),
+            state_path: None,
+            options: Vec::new(),
+        })
+        .await
+        .unwrap();
+
+        // Check the execution and its fee, as the network would.
+        let execution = transaction.execution().unwrap();
+        assert_eq!(execution.to_execution_id().unwrap(), execution_id);
+        assert_eq!(execution.global_state_root(), state_root);
+        let fee = transaction.fee_transition().unwrap();
+        assert_eq!(fee.deployment_or_execution_id().unwrap(), execution_id);
+        let process = Process::<CurrentNetwork>::load().unwrap();
+        execute_service::verify_transaction(&process, execution, &fee).unwrap();
+    }
+}
@@ -0,0 +1,184 @@
+// Copyright (C) 2019-2024 Aleo Systems Inc.
+// This file is part of the Aleo SDK library.
+
+// The Aleo SDK library is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// The Aleo SDK library is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with the Aleo SDK library. If not, see <https://www.gnu.org/licenses/>.
+
+use super::*;
+
+use std::path::{Path, PathBuf};
+
+/// A TOML file holding the settings shared by every command, e.g.
+///
+/// ```toml
+/// network = "testnet"
+/// authorize_url = "http://localhost:8080"
+/// execute_urls = ["http://localhost:8081", "http://localhost:8082"]
+/// node_url = "http://localhost:3030"
+/// private_key_file = "/secrets/aleo.key"
+/// wallet = "/home/alice/.aleo/wallet.json"
+/// account = "main"
+/// base_fee = 300000
+/// priority_fee = 10
+/// timeout_secs = 600
+/// max_attempts = 3
+/// execute_cooldown_secs = 30
+/// local = false
+/// ```
+#[derive(Clone, Debug, Default, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct Profile {
+    pub network: Option<String>,
+    pub authorize_url: Option<String>,
+    pub execute_urls: Option<Vec<String>>,
+    pub node_url: Option<String>,
+    pub private_key: Option<String>,
+    pub private_key_file: Option<PathBuf>,
+    pub wallet: Option<PathBuf>,
+    pub account: Option<String>,
+    pub base_fee: Option<u64>,
+    pub priority_fee: Option<u64>,
+    pub timeout_secs: Option<u64>,
+    pub max_attempts: Option<u32>,
+    pub execute_cooldown_secs: Option<u64>,
+    pub local: Option<bool>,
+}
+
+impl Profile {
+    /// Reads the profile at `path`.
+    pub fn load(path: &Path) -> Result<Self> {
+        let contents = std::fs::read_to_string(path)
+            .map_err(|error| anyhow!("Failed to read {}: {error}", path.display()))?;
+        toml::from_str(&contents)
+            .map_err(|error| anyhow!("Failed to parse {}: {error}", path.display()))
+    }
+}
+
+/// The settings of a client run, resolved from the flags, the profile and the defaults.
+#[derive(Clone, Debug)]
+pub struct Context {
+    pub client: Client,
+    pub network: String,
+    pub authorize_url: String,
+    pub execute_urls: Vec<String>,
+    pub node_url: String,
+    pub base_fee: u64,
+    pub priority_fee: u64,
+    pub timeout: Duration,
+    pub max_attempts: u32,
+    pub execute_cooldown: Duration,
+    pub local: bool,
+    pub wallet: PathBuf,
+    pub account: Option<String>,
+    private_key: Option<String>,
+}
+
+impl Context {
+    /// Resolves the settings, with the flags taking precedence over the profile.
+    pub fn new(opt: &Opt, profile: Profile) -> Result<Self> {
+        let private_key = match (&opt.private_key, &opt.private_key_file) {
+            (Some(private_key), _) => Some(private_key.clone()),
+            (None, Some(path)) => Some(read_private_key(path)?),
+            (None, None) => match (profile.private_key, profile.private_key_file) {
+                (Some(private_key), _) => Some(private_key),
+                (None, Some(path)) => Some(read_private_key(&path)?),
+                (None, None) => None,
+            },
+        };
+        let execute_urls = match (opt.execute_urls.is_empty(), profile.execute_urls) {
+            (false, _) => opt.execute_urls.clone(),
+            (true, Some(urls)) => urls,
+            (true, None) => vec!["http://localhost:8081".to_string()],
+        };
+        let execute_urls = execute_urls
+            .into_iter()
+            .filter_map(|url| trim_url(Some(url)))
+            .collect();
+        Ok(Self {
+            client: Client::new(),
+            network: opt
+                .network
+                .clone()
+                .or(profile.network)
+                .unwrap_or_else(|| "testnet".to_string()),
+            authorize_url: trim_url(opt.authorize_url.clone().or(profile.authorize_url))
+                .unwrap_or_else(|| "http://localhost:8080".to_string()),
+            execute_urls,
+            node_url: trim_url(opt.node_url.clone().or(profile.node_url))
+                .unwrap_or_else(|| "http://localhost:3030".to_string()),
+            base_fee: opt.base_fee.or(profile.base_fee).unwrap_or(300_000),
+            priority_fee: opt.priority_fee.or(profile.priority_fee).unwrap_or(10),
+            timeout: Duration::from_secs(profile.timeout_secs.unwrap_or(600)),
+            max_attempts: profile.max_attempts.unwrap_or(3),
+            execute_cooldown: Duration::from_secs(profile.execute_cooldown_secs.unwrap_or(30)),
+            local: opt.local || profile.local.unwrap_or(false),
+            wallet: opt
+                .wallet
+                .clone()
+                .or(profile.wallet)
+                .unwrap_or_else(default_wallet_path),
+            account: opt.account.clone().or(profile.account),
+            private_key,
+        })
+    }
+
+    /// Returns the configured private key, or the wallet account.
+    pub fn private_key<N: Network>(&self) -> Result<PrivateKey<N>> {
+        match &self.private_key {
+            Some(private_key) => PrivateKey::from_str(private_key),
+            None if self.wallet.exists() => {
+                let wallet = Wallet::load(&self.wallet)?;
+                wallet.private_key(self.account.as_deref(), &read_password(false)?)
+            }
+            None => bail!(
+                "No private key is configured, pass --private-key-file, set one in the profile \
+                 or add one to the wallet with `wallet create`"
+            ),
+        }
+    }
+
+    /// Returns a client for the services and the node.
+    pub fn service_client<N: Network>(&self) -> Result<ServiceClient<N>> {
+        Ok(ServiceClient::new(self.client_config())?)
+    }
+
+    /// Returns the endpoints, timeout and retry policy of the service client.
+    pub fn client_config(&self) -> ClientConfig {
+        ClientConfig {
+            authorize_url: self.authorize_url.clone(),
+            execute_urls: self.execute_urls.clone(),
+            node_url: self.node_url.clone(),
+            timeout: self.timeout,
+            max_attempts: self.max_attempts,
+            cooldown: self.execute_cooldown,
+            ..Default::default()
+        }
+    }
+
+    /// Returns the URL of a node endpoint, e.g. `stateRoot/latest`.
+    pub fn node_endpoint(&self, path: &str) -> String {
+        format!("{}/{}/{}", self.node_url, self.network, path)
+    }
+}
+
+// Reads a private key from a file, ignoring surrounding whitespace.
+fn read_private_key(path: &Path) -> Result<String> {
+    let private_key = std::fs::read_to_string(path)
+        .map_err(|error| anyhow!("Failed to read {}: {error}", path.display()))?;
+    Ok(private_key.trim().to_string())
+}
+
+// Drops the trailing slash of a base URL, so that paths can be appended with `/`.
+fn trim_url(url: Option<String>) -> Option<String> {
+    url.map(|url| url.trim_end_matches('/').to_string())
+}
@@ -0,0 +1,384 @@
+// Copyright (C) 2019-2024 Aleo Systems Inc.
+// This file is part of the Aleo SDK library.
+
+// The Aleo SDK library is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// The Aleo SDK library is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with the Aleo SDK library. If not, see <https://www.gnu.org/licenses/>.
+
+use super::*;
+
+use argon2::{Algorithm, Argon2, Params, Version};
+use chacha20poly1305::aead::{Aead, KeyInit, Payload};
+use chacha20poly1305::{ChaCha20Poly1305, Key, Nonce};
+use rand_chacha::rand_core::RngCore;
+use std::path::{Path, PathBuf};
+
+/// The version of the wallet file format.
+pub const WALLET_VERSION: u16 = 1;
+
+/// The environment variable holding the wallet password, for scripts and CI.
+pub const WALLET_PASSWORD_ENV: &str = "ALEO_WALLET_PASSWORD";
+
+/// A JSON file holding named accounts. Each private key is encrypted with ChaCha20-Poly1305
+/// under a key derived from the wallet password with Argon2id; names and addresses are
+/// stored in the clear so that accounts can be listed without the password.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct Wallet {
+    pub version: u16,
+    pub kdf: KdfParams,
+    pub active: Option<String>,
+    pub accounts: Vec<Account>,
+}
+
+/// The Argon2id parameters deriving the encryption key from the password.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct KdfParams {
+    /// The hex-encoded salt.
+    pub salt: String,
+    /// The memory cost in KiB.
+    pub m_cost: u32,
+    pub t_cost: u32,
+    pub p_cost: u32,
+}
+
+/// A named account and its encrypted private key.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct Account {
+    pub name: String,
+    pub address: String,
+    /// The hex-encoded nonce.
+    pub nonce: String,
+    /// The hex-encoded private key, encrypted with the address as associated data.
+    pub ciphertext: String,
+}
+
+impl KdfParams {
+    /// Returns the default Argon2id parameters with a random salt.
+    fn new() -> Self {
+        Self {
+            salt: hex::encode(random_bytes::<16>()),
+            m_cost: Params::DEFAULT_M_COST,
+            t_cost: Params::DEFAULT_T_COST,
+            p_cost: Params::DEFAULT_P_COST,
+        }
+    }
+}
+
+impl Wallet {
+    /// Returns an empty wallet.
+    pub fn new() -> Self {
+        Self::with_kdf(KdfParams::new())
+    }
+
+    // Returns an empty wallet deriving its key with `kdf`.
+    fn with_kdf(kdf: KdfParams) -> Self {
+        Self {
+            version: WALLET_VERSION,
+            kdf,
+            active: None,
+            accounts: Vec::new(),
+        }
+    }
+
+    /// Reads the wallet at `path`.
+    pub fn load(path: &Path) -> Result<Self> {
+        let contents = std::fs::read_to_string(path)
+            .map_err(|error| anyhow!("Failed to read {}: {error}", path.display()))?;
+        let wallet = serde_json::from_str::<Self>(&contents)
+            .map_err(|error| anyhow!("Failed to parse {}: {error}", path.display()))?;
+        ensure!(
+            wallet.version == WALLET_VERSION,
+            "Unsupported wallet version {}",
+            wallet.version
+        );
+        Ok(wallet)
+    }
+
+    /// Writes the wallet to `path`, readable only by the current user.
+    pub fn save(&self, path: &Path) -> Result<()> {
+        if let Some(parent) = path
+            .parent()
+            .filter(|parent| !parent.as_os_str().is_empty())
+        {
+            std::fs::create_dir_all(parent)?;
+        }
+        // Write a temporary file first, so that a failed write does not lose the wallet.
+        let temporary = path.with_extension("tmp");
+        write_private_file(&temporary, serde_json::to_string_pretty(self)?.as_bytes())?;
+        std::fs::rename(&temporary, path)
+            .map_err(|error| anyhow!("Failed to write {}: {error}", path.display()))
+    }
+
+    /// Encrypts the private key under `name`, making it active if it is the first account.
+    pub fn add<N: Network>(
+        &mut self,
+        name: &str,
+        private_key: &PrivateKey<N>,
+        password: &str,
+    ) -> Result<()> {
+        ensure!(!name.is_empty(), "The account name must not be empty");
+        ensure!(
+            self.accounts.iter().all(|account| account.name != name),
+            "The wallet already has an account named '{name}'"
+        );
+        let key = self.key(password)?;
+        // Check the password against an existing account, so that one password opens every account.
+        if let Some(account) = self.accounts.first() {
+            decrypt(&key, account)?;
+        }
+
+        let address = Address::<N>::try_from(private_key)?.to_string();
+        let nonce = random_bytes::<12>();
+        let ciphertext = ChaCha20Poly1305::new(Key::from_slice(&key))
+            .encrypt(
+                Nonce::from_slice(&nonce),
+                Payload {
+                    msg: private_key.to_string().as_bytes(),
+                    aad: address.as_bytes(),
+                },
+            )
+            .map_err(|_| anyhow!("Failed to encrypt the private key"))?;
+        self.accounts.push(Account {
+            name: name.to_string(),
+            address,
+            nonce: hex::encode(nonce),
+            ciphertext: hex::encode(ciphertext),
+        });
+        if self.active.is_none() {
+            self.active = Some(name.to_string());
+        }
+        Ok(())
+    }
+
+    /// Decrypts the private key of the named account, or of the active account.
+    pub fn private_key<N: Network>(
+        &self,
+        name: Option<&str>,
+        password: &str,
+    ) -> Result<PrivateKey<N>> {
+        let account = self.account(name)?;
+        let private_key = decrypt(&self.key(password)?, account)?;
+        PrivateKey::from_str(&private_key)
+    }
+
+    /// Returns the named account, or the active account.
+    pub fn account(&self, name: Option<&str>) -> Result<&Account> {
+        let name = match name.or(self.active.as_deref()) {
+            Some(name) => name,
+            None => bail!("The wallet has no active account, pick one with `wallet use`"),
+        };
+        self.accounts
+            .iter()
+            .find(|account| account.name == name)
+            .ok_or_else(|| anyhow!("The wallet has no account named '{name}'"))
+    }
+
+    /// Makes the named account the one used by default.
+    pub fn set_active(&mut self, name: &str) -> Result<()> {
+        self.account(Some(name))?;
+        self.active = Some(name.to_string());
+        Ok(())
+    }
+
+    // Derives the encryption key from the password.
+    fn key(&self, password: &str) -> Result<[u8; 32]> {
+        let salt = hex::decode(&self.kdf.salt)?;
+        let params = Params::new(self.kdf.m_cost, self.kdf.t_cost, self.kdf.p_cost, Some(32))
+            .map_err(|error| anyhow!("Invalid wallet key derivation parameters: {error}"))?;
+        let mut key = [0u8; 32];
+        Argon2::new(Algorithm::Argon2id, Version::V0x13, params)
+            .hash_password_into(password.as_bytes(), &salt, &mut key)
+            .map_err(|error| anyhow!("Failed to derive the wallet key: {error}"))?;
+        Ok(key)
+    }
+}
+
+impl Default for Wallet {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Returns the default wallet path, `~/.aleo/wallet.json`.
+pub fn default_wallet_path() -> PathBuf {
+    std::env::var_os("HOME")
+        .map(PathBuf::from)
+        .unwrap_or_default()
+        .join(".aleo")
+        .join("wallet.json")
+}
+
+/// Reads the wallet password from `ALEO_WALLET_PASSWORD`, or prompts for it.
+/// With `confirm`, the prompt asks twice, as when the wallet is created.
+pub fn read_password(confirm: bool) -> Result<String> {
+    if let Ok(password) = std::env::var(WALLET_PASSWORD_ENV) {
+        return Ok(password);
+    }
+    let password = rpassword::prompt_password("Wallet password: ")?;
+    if confirm {
+        let repeated = rpassword::prompt_password("Repeat the wallet password: ")?;
+        ensure!(password == repeated, "The passwords do not match");
+    }
+    Ok(password)
+}
+
+/// Writes `contents` to `path`, readable only by the current user, even if the file exists.
+pub fn write_private_file(path: &Path, contents: &[u8]) -> Result<()> {
+    let mut options = std::fs::OpenOptions::new();
+    options.write(true).create(true).truncate(true);
+    #[cfg(unix)]
+    std::os::unix::fs::OpenOptionsExt::mode(&mut options, 0o600);
+    let mut file = options
+        .open(path)
+        .map_err(|error| anyhow!("Failed to write {}: {error}", path.display()))?;
+    // The mode only applies to new files, so restrict an existing one as well.
+    #[cfg(unix)]
+    file.set_permissions(std::os::unix::fs::PermissionsExt::from_mode(0o600))?;
+    std::io::Write::write_all(&mut file, contents)?;
+    file.sync_all()?;
+    Ok(())
+}
+
+// Decrypts the private key of the account.
+fn decrypt(key: &[u8; 32], account: &Account) -> Result<String> {
+    let nonce = hex::decode(&account.nonce)?;
+    ensure!(
+        nonce.len() == 12,
+        "Invalid nonce for account '{}'",
+        account.name
+    );
+    let plaintext = ChaCha20Poly1305::new(Key::from_slice(key))
+        .decrypt(
+            Nonce::from_slice(&nonce),
+            Payload {
+                msg: &hex::decode(&account.ciphertext)?,
+                aad: account.address.as_bytes(),
+            },
+        )
+        .map_err(|_| anyhow!("Wrong password, or the wallet is corrupted"))?;
+    Ok(String::from_utf8(plaintext)?)
+}
+
+// Returns `L` random bytes.
+fn random_bytes<const L: usize>() -> [u8; L] {
+    let mut bytes = [0u8; L];
+    rand_chacha::ChaCha20Rng::from_entropy().fill_bytes(&mut bytes);
+    bytes
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use snarkvm::prelude::TestnetV0;
+
+    type CurrentNetwork = TestnetV0;
+
+    // Returns an empty wallet with cheap key derivation, to keep the tests fast.
+    fn sample_wallet() -> Wallet {
+        Wallet::with_kdf(KdfParams {
+            m_cost: 8,
+            t_cost: 1,
+            ..KdfParams::new()
+        })
+    }
+
+    #[test]
+    fn test_add_and_decrypt_accounts() {
+        let rng = &mut rand_chacha::ChaCha20Rng::seed_from_u64(1);
+        let first = PrivateKey::<CurrentNetwork>::new(rng).unwrap();
+        let second = PrivateKey::<CurrentNetwork>::new(rng).unwrap();
+
+        let mut wallet = sample_wallet();
+        wallet.add("first", &first, "password").unwrap();
+        wallet.add("second", &second, "password").unwrap();
+        assert_eq!(wallet.active.as_deref(), Some("first"));
+        assert_eq!(
+            wallet.account(Some("second")).unwrap().address,
+            Address::try_from(&second).unwrap().to_string()
+        );
+
+        let decrypted = wallet
+            .private_key::<CurrentNetwork>(None, "password")
+            .unwrap();
+        assert_eq!(decrypted, first);
+        wallet.set_active("second").unwrap();
+        let decrypted = wallet
+            .private_key::<CurrentNetwork>(None, "password")
+            .unwrap();
+        assert_eq!(decrypted, second);
+
+        // The private key is never stored in the clear.
+        let json = serde_json::to_string(&wallet).unwrap();
+        assert!(!json.contains(&first.to_string()));
+        assert!(!json.contains(&second.to_string()));
+    }
+
+    #[test]
+    fn test_rejects_wrong_password_and_tampering() {
+        let rng = &mut rand_chacha::ChaCha20Rng::seed_from_u64(1);
+        let private_key = PrivateKey::<CurrentNetwork>::new(rng).unwrap();
+
+        let mut wallet = sample_wallet();
+        wallet.add("main", &private_key, "password").unwrap();
+        assert!(wallet.private_key::<CurrentNetwork>(None, "wrong").is_err());
+        // Adding an account under another password would leave the wallet with two passwords.
+        assert!(wallet.add("other", &private_key, "wrong").is_err());
+        assert!(wallet.add("main", &private_key, "password").is_err());
+
+        // The ciphertext is bound to the stored address.
+        let other = PrivateKey::<CurrentNetwork>::new(rng).unwrap();
+        wallet.accounts[0].address = Address::try_from(&other).unwrap().to_string();
+        assert!(wallet
+            .private_key::<CurrentNetwork>(None, "password")
+            .is_err());
+    }
+
+    #[test]
+    fn test_save_and_load() {
+        let rng = &mut rand_chacha::ChaCha20Rng::seed_from_u64(1);
+        let private_key = PrivateKey::<CurrentNetwork>::new(rng).unwrap();
+        let mut wallet = sample_wallet();
+        wallet.add("main", &private_key, "password").unwrap();
+
+        let path = std::env::temp_dir()
+            .join(format!("wallet-test-{}", std::process::id()))
+            .join("wallet.json");
+        wallet.save(&path).unwrap();
+        let loaded = Wallet::load(&path).unwrap();
+        std::fs::remove_dir_all(path.parent().unwrap()).unwrap();
+
+        assert_eq!(
+            loaded
+                .private_key::<CurrentNetwork>(Some("main"), "password")
+                .unwrap(),
+            private_key
+        );
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_private_files_are_owner_only() {
+        use std::os::unix::fs::PermissionsExt;
+
+        let path = std::env::temp_dir().join(format!("private-file-test-{}", std::process::id()));
+        std::fs::write(&path, "previous").unwrap();
+        std::fs::set_permissions(&path, std::fs::Permissions::from_mode(0o644)).unwrap();
+        write_private_file(&path, b"secret").unwrap();
+        let mode = std::fs::metadata(&path).unwrap().permissions().mode();
+        let contents = std::fs::read_to_string(&path).unwrap();
+        std::fs::remove_file(&path).unwrap();
+
+        assert_eq!(mode & 0o777, 0o600);
+        assert_eq!(contents, "secret");
+    }
+}
@@ -0,0 +1,92 @@
+// Copyright (C) 2019-2024 Aleo Systems Inc.
+// This file is part of the Aleo SDK library.
+
+// The Aleo SDK library is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// The Aleo SDK library is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with the Aleo SDK library. If not, see <https://www.gnu.org/licenses/>.
+
+//! Drives the client against an execute service that forwards the transactions it proves to
+//! the stub node. The service settings are global, so this runs in its own test binary.
+
+mod common;
+use common::*;
+
+use execute_service::{set_settings, BroadcastSettings, Settings};
+use transfer_client::{
+    check_transaction, request_authorization, request_execution, submit, WaitArgs,
+};
+
+use snarkvm::prelude::{Address, Field, PrivateKey, Value};
+use std::str::FromStr;
+use std::time::Duration;
+
+#[tokio::test(flavor = "multi_thread")]
+async fn test_execute_service_broadcasts_the_transaction() {
+    let node = StubNode::spawn();
+    set_settings(Settings {
+        broadcast: Some(BroadcastSettings {
+            url: format!("http://{}/testnet/transaction/broadcast", node.address),
+            max_attempts: 1,
+            initial_backoff: Duration::from_millis(10),
+            timeout: Duration::from_secs(5),
+        }),
+        ..Default::default()
+    })
+    .unwrap();
+    let context = client_context(&node);
+
+    // Authorize a public transfer to the same account.
+    let private_key = PrivateKey::<CurrentNetwork>::try_from(Field::from_u64(1234)).unwrap();
+    let address = Address::try_from(&private_key).unwrap();
+    let inputs = vec![
+        Value::from_str(&address.to_string()).unwrap(),
+        Value::from_str("100u64").unwrap(),
+    ];
+    let authorization = request_authorization(
+        &context,
+        private_key,
+        "credits.aleo",
+        "transfer_public",
+        inputs,
+    )
+    .await
+    .unwrap();
+    let execution_id = authorization
+        .function_authorization
+        .to_execution_id()
+        .unwrap();
+
+    // The service forwards the transaction it proves, and replies with it and the node's answer.
+    let outcome = request_execution(&context, authorization).await.unwrap();
+    let transaction = outcome.transaction;
+    check_transaction(&transaction, Some(&execution_id)).unwrap();
+    let broadcast = outcome.broadcast.unwrap();
+    assert!(broadcast.is_accepted());
+    assert_eq!(broadcast.attempts, 1);
+    assert_eq!(
+        transaction.execution().unwrap().global_state_root(),
+        node.state_root
+    );
+
+    // The client does not broadcast a transaction the node already accepted from the service.
+    let wait = WaitArgs {
+        wait: true,
+        wait_timeout_secs: 5,
+        poll_interval_ms: 10,
+    };
+    submit(&context, &transaction, Some(&broadcast), &wait)
+        .await
+        .unwrap();
+    let broadcasts = node.broadcasts.lock().unwrap();
+    assert_eq!(broadcasts.len(), 1);
+    assert_eq!(broadcasts[0].id(), transaction.id());
+}
@@ -0,0 +1,135 @@
+// Copyright (C) 2019-2024 Aleo Systems Inc.
+// This file is part of the Aleo SDK library.
+
+// The Aleo SDK library is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// The Aleo SDK library is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with the Aleo SDK library. If not, see <https://www.gnu.org/licenses/>.
+
+//! The authorize and execute services running in-process on ephemeral ports, a stub node,
+//! and a client context pointing at them.
+
+use transfer_client::{Context, Opt, Profile};
+
+use snarkvm::ledger::block::Transaction;
+use snarkvm::prelude::{Field, Network, TestnetV0};
+use std::net::SocketAddr;
+use std::sync::{Arc, Mutex};
+use structopt::StructOpt;
+use warp::Filter;
+
+pub type CurrentNetwork = TestnetV0;
+
+/// A node that serves a fixed state root, records the transactions broadcast to it and
+/// confirms them.
+pub struct StubNode {
+    pub address: SocketAddr,
+    pub state_root: <CurrentNetwork as Network>::StateRoot,
+    pub broadcasts: Arc<Mutex<Vec<Transaction<CurrentNetwork>>>>,
+}
+
+impl StubNode {
+    pub fn spawn() -> Self {
+        let state_root = <CurrentNetwork as Network>::StateRoot::from(Field::from_u64(7));
+        let broadcasts = Arc::new(Mutex::new(Vec::new()));
+
+        let state_root_route = warp::get()
+            .and(warp::path!("testnet" / "stateRoot" / "latest"))
+            .map(move || warp::reply::json(&state_root));
+        let recorded = broadcasts.clone();
+        let broadcast_route = warp::post()
+            .and(warp::path!("testnet" / "transaction" / "broadcast"))
+            .and(warp::body::json())
+            .map(move |transaction: Transaction<CurrentNetwork>| {
+                let id = transaction.id().to_string();
+                recorded.lock().unwrap().push(transaction);
+                warp::reply::json(&id)
+            });
+        // A broadcast transaction is confirmed in the next block.
+        let recorded = broadcasts.clone();
+        let confirmed_route = warp::get()
+            .and(warp::path!(
+                "testnet" / "transaction" / "confirmed" / String
+            ))
+            .map(move |id: String| {
+                let broadcasts = recorded.lock().unwrap();
+                match broadcasts
+                    .iter()
+                    .any(|transaction: &Transaction<CurrentNetwork>| {
+                        transaction.id().to_string() == id
+                    }) {
+                    true => warp::reply::with_status(
+                        warp::reply::json(&serde_json::json!({
+                            "status": "accepted",
+                            "type": "execute",
+                            "index": 0,
+                        })),
+                        warp::http::StatusCode::OK,
+                    ),
+                    false => warp::reply::with_status(
+                        warp::reply::json(&"Missing transaction"),
+                        warp::http::StatusCode::NOT_FOUND,
+                    ),
+                }
+            });
+        let block_hash_route = warp::get()
+            .and(warp::path!("testnet" / "find" / "blockHash" / String))
+            .map(|_: String| warp::reply::json(&"ab1stub"));
+        let height_route = warp::get()
+            .and(warp::path!("testnet" / "height" / String))
+            .map(|_: String| warp::reply::json(&1u32));
+        let routes = state_root_route
+            .or(broadcast_route)
+            .or(confirmed_route)
+            .or(block_hash_route)
+            .or(height_route);
+        let (address, server) = warp::serve(routes).bind_ephemeral(([127, 0, 0, 1], 0));
+        tokio::spawn(server);
+
+        Self {
+            address,
+            state_root,
+            broadcasts,
+        }
+    }
+}
+
+// Starts the authorize service routes, returning their address.
+fn spawn_authorize_service() -> SocketAddr {
+    let routes = authorize_service::keygen_route::<CurrentNetwork>()
+        .or(authorize_service::authorize_route::<CurrentNetwork>())
+        .recover(authorize_service::handle_shutdown_rejection);
+    let (address, server) = warp::serve(routes).bind_ephemeral(([127, 0, 0, 1], 0));
+    tokio::spawn(server);
+    address
+}
+
+// Starts the execute service routes, returning their address.
+fn spawn_execute_service() -> SocketAddr {
+    let routes = execute_service::execute_route::<CurrentNetwork>()
+        .recover(execute_service::handle_shutdown_rejection);
+    let (address, server) = warp::serve(routes).bind_ephemeral(([127, 0, 0, 1], 0));
+    tokio::spawn(server);
+    address
+}
+
+/// Starts both services and returns the context of a client that uses them and the node,
+/// as the command line would.
+pub fn client_context(node: &StubNode) -> Context {
+    let opt = Opt::from_iter([
+        "transfer-client".to_string(),
+        format!("--authorize-url=http://{}", spawn_authorize_service()),
+        format!("--execute-url=http://{}", spawn_execute_service()),
+        format!("--node-url=http://{}", node.address),
+        "status".to_string(),
+    ]);
+    Context::new(&opt, Profile::default()).unwrap()
+}
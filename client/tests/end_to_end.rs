@@ -0,0 +1,90 @@
+// Copyright (C) 2019-2024 Aleo Systems Inc.
+// This file is part of the Aleo SDK library.
+
+// The Aleo SDK library is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// The Aleo SDK library is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with the Aleo SDK library. If not, see <https://www.gnu.org/licenses/>.
+
+//! Drives the client through keygen, authorize, execute and broadcast, against both services
+//! running in-process on ephemeral ports and a stub node.
+
+mod common;
+use common::*;
+
+use transfer_client::{
+    check_authorizations, check_transaction, request_authorization, request_execution,
+    request_keygen, submit, verify_proofs, WaitArgs,
+};
+
+use snarkvm::prelude::{Address, Value};
+use std::str::FromStr;
+
+#[tokio::test(flavor = "multi_thread")]
+async fn test_keygen_authorize_execute_broadcast() {
+    let node = StubNode::spawn();
+    let context = client_context(&node);
+
+    // Generate a key from a fixed seed.
+    let (private_key, address) =
+        request_keygen::<CurrentNetwork>(&context, Some("1234field".to_string()))
+            .await
+            .unwrap();
+    assert_eq!(address, Address::try_from(&private_key).unwrap());
+
+    // Authorize a public transfer to the same account.
+    let inputs = vec![
+        Value::from_str(&address.to_string()).unwrap(),
+        Value::from_str("100u64").unwrap(),
+    ];
+    let authorization = request_authorization(
+        &context,
+        private_key,
+        "credits.aleo",
+        "transfer_public",
+        inputs,
+    )
+    .await
+    .unwrap();
+    let execution_id = check_authorizations(
+        &authorization.function_authorization,
+        &authorization.fee_authorization,
+        None,
+    )
+    .unwrap();
+
+    // Prove the transaction against the state root of the node.
+    let outcome = request_execution(&context, authorization).await.unwrap();
+    assert!(outcome.broadcast.is_none());
+    let transaction = outcome.transaction;
+    check_transaction(&transaction, Some(&execution_id)).unwrap();
+    let execution = transaction.execution().unwrap();
+    assert_eq!(execution.global_state_root(), node.state_root);
+    let transition = execution.peek().unwrap();
+    assert_eq!(transition.program_id().to_string(), "credits.aleo");
+    assert_eq!(transition.function_name().to_string(), "transfer_public");
+    let fee = transaction.fee_transition().unwrap();
+    assert_eq!(*fee.base_amount().unwrap(), 300_000);
+
+    // Check the proofs, as the network would.
+    verify_proofs(&transaction).unwrap();
+
+    // Broadcast the transaction and wait for the node to confirm it.
+    let wait = WaitArgs {
+        wait: true,
+        wait_timeout_secs: 5,
+        poll_interval_ms: 10,
+    };
+    submit(&context, &transaction, None, &wait).await.unwrap();
+    let broadcasts = node.broadcasts.lock().unwrap();
+    assert_eq!(broadcasts.len(), 1);
+    assert_eq!(broadcasts[0].id(), transaction.id());
+}
@@ -17,7 +17,7 @@
 #[macro_use]
 extern crate criterion;
 
-use execute_service::{execute, ExecuteRequest};
+use execute_service::{execute, execute_with_proving, ExecuteRequest, ProvingMode};
 
 use snarkvm::circuit::AleoV0;
 use snarkvm::prelude::{Address, Literal, MainnetV0, PrivateKey, Process, ToBytes, Value, U64};
@@ -29,7 +29,7 @@ use warp::hyper::body::Bytes;
 
 type CurrentNetwork = MainnetV0;
 
-fn bench_execute_transfer_public(c: &mut Criterion) {
+fn transfer_public_request() -> Bytes {
     let process = Process::<CurrentNetwork>::load().unwrap();
     let private_key =
         PrivateKey::from_str("APrivateKey1zkpCE9rCw9SixY82xaDrW2Hwxc2f3VjeuR2oZHR81zcuUDV")
@@ -61,8 +61,13 @@ fn bench_execute_transfer_public(c: &mut Criterion) {
         fee_authorization,
         state_root: None,
         state_path: None,
+        options: Vec::new(),
     };
-    let bytes = Bytes::copy_from_slice(&request.to_bytes_le().unwrap());
+    Bytes::copy_from_slice(&request.to_bytes_le().unwrap())
+}
+
+fn bench_execute_transfer_public(c: &mut Criterion) {
+    let bytes = transfer_public_request();
     c.bench_function("authorize_transfer_public", |b| {
         b.iter_batched(
             || bytes.clone(),
@@ -71,9 +76,28 @@ fn bench_execute_transfer_public(c: &mut Criterion) {
         )
     });
 }
+
+fn bench_proving_modes_transfer_public(c: &mut Criterion) {
+    let bytes = transfer_public_request();
+    let mut group = c.benchmark_group("prove_transfer_public");
+    for (name, proving) in [
+        ("sequential", ProvingMode::Sequential),
+        ("parallel", ProvingMode::Parallel),
+    ] {
+        group.bench_function(name, |b| {
+            b.iter_batched(
+                || bytes.clone(),
+                |bytes| execute_with_proving::<CurrentNetwork>(bytes, proving).unwrap(),
+                BatchSize::SmallInput,
+            )
+        });
+    }
+    group.finish();
+}
+
 criterion_group! {
     name = routes;
     config = Criterion::default();
-    targets = bench_execute_transfer_public,
+    targets = bench_execute_transfer_public, bench_proving_modes_transfer_public,
 }
 criterion_main!(routes);
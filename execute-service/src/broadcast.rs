@@ -0,0 +1,219 @@
+// Copyright (C) 2019-2024 Aleo Systems Inc.
+// This file is part of the Aleo SDK library.
+
+// The Aleo SDK library is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// The Aleo SDK library is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with the Aleo SDK library. If not, see <https://www.gnu.org/licenses/>.
+
+use super::*;
+
+use std::time::Duration;
+
+/// Where finished transactions are forwarded, and how failed attempts are retried.
+#[derive(Clone, Debug)]
+pub struct BroadcastSettings {
+    /// The node endpoint that accepts transactions, e.g. `http://localhost:3030/mainnet/transaction/broadcast`.
+    pub url: String,
+    /// The maximum number of attempts, including the first one.
+    pub max_attempts: u32,
+    /// The delay before the first retry. The delay doubles after every failed attempt,
+    /// up to `MAX_BROADCAST_BACKOFF`.
+    pub initial_backoff: Duration,
+    /// The time allowed for each attempt, including reading the node's reply.
+    pub timeout: Duration,
+}
+
+/// The longest delay between two broadcast attempts.
+pub const MAX_BROADCAST_BACKOFF: Duration = Duration::from_secs(30);
+
+/// The time allowed for each broadcast attempt when none is configured.
+pub const DEFAULT_BROADCAST_TIMEOUT: Duration = Duration::from_secs(30);
+
+/// The reply of the node to a forwarded transaction.
+#[derive(Clone, Debug)]
+pub struct NodeReply {
+    pub status: u16,
+    pub body: String,
+    pub attempts: u32,
+}
+
+/// Returns a client for forwarding transactions, bounding each attempt by the configured timeout
+/// so that a node that hangs cannot hang the request that produced the transaction.
+pub fn broadcast_client() -> reqwest::Client {
+    let timeout = settings()
+        .broadcast
+        .as_ref()
+        .map_or(DEFAULT_BROADCAST_TIMEOUT, |broadcast| broadcast.timeout);
+    reqwest::Client::builder()
+        .timeout(timeout)
+        .build()
+        .expect("Failed to build the broadcast client")
+}
+
+/// Posts `body` as JSON to the broadcast URL, retrying with exponential backoff.
+/// Connection failures and server errors are retried; any other reply from the node is final.
+pub async fn broadcast<T: Serialize + ?Sized>(
+    client: &reqwest::Client,
+    settings: &BroadcastSettings,
+    body: &T,
+) -> Result<NodeReply> {
+    let mut backoff = settings.initial_backoff;
+    let mut attempt = 1;
+    loop {
+        match client.post(&settings.url).json(body).send().await {
+            Ok(response)
+                if !response.status().is_server_error() || attempt >= settings.max_attempts =>
+            {
+                let status = response.status().as_u16();
+                let body = response.text().await?;
+                return Ok(NodeReply {
+                    status,
+                    body,
+                    attempts: attempt,
+                });
+            }
+            Ok(response) => log::warn!(
+                "Broadcast attempt {attempt} failed with status: {}",
+                response.status()
+            ),
+            Err(error) if attempt >= settings.max_attempts => return Err(error.into()),
+            Err(error) => log::warn!("Broadcast attempt {attempt} failed: {error}"),
+        }
+        tokio::time::sleep(backoff).await;
+        backoff = next_backoff(backoff);
+        attempt += 1;
+    }
+}
+
+/// Doubles the delay between attempts, up to `MAX_BROADCAST_BACKOFF`.
+pub fn next_backoff(backoff: Duration) -> Duration {
+    backoff.saturating_mul(2).min(MAX_BROADCAST_BACKOFF)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use std::sync::atomic::{AtomicU32, Ordering};
+    use std::sync::Arc;
+    use warp::{http::StatusCode, Filter};
+
+    // Starts a stub node that answers the n-th broadcast attempt with `status(n)`.
+    fn stub_node(
+        status: impl Fn(u32) -> StatusCode + Clone + Send + Sync + 'static,
+    ) -> (BroadcastSettings, Arc<AtomicU32>) {
+        let attempts = Arc::new(AtomicU32::new(0));
+        let counter = attempts.clone();
+        let route = warp::post()
+            .and(warp::path!("transaction" / "broadcast"))
+            .and(warp::body::bytes())
+            .map(move |_| {
+                let attempt = counter.fetch_add(1, Ordering::SeqCst) + 1;
+                warp::reply::with_status(warp::reply::json(&"at1stub"), status(attempt))
+            });
+        let (address, server) = warp::serve(route).bind_ephemeral(([127, 0, 0, 1], 0));
+        tokio::spawn(server);
+        let settings = BroadcastSettings {
+            url: format!("http://{address}/transaction/broadcast"),
+            max_attempts: 3,
+            initial_backoff: Duration::from_millis(10),
+            timeout: Duration::from_secs(5),
+        };
+        (settings, attempts)
+    }
+
+    #[tokio::test]
+    async fn test_broadcast_retries_server_errors() {
+        let (settings, attempts) = stub_node(|attempt| match attempt {
+            1 | 2 => StatusCode::SERVICE_UNAVAILABLE,
+            _ => StatusCode::OK,
+        });
+        let reply = broadcast(&reqwest::Client::new(), &settings, "transaction")
+            .await
+            .unwrap();
+        assert_eq!(reply.status, 200);
+        assert_eq!(reply.body, "\"at1stub\"");
+        assert_eq!(reply.attempts, 3);
+        assert_eq!(attempts.load(Ordering::SeqCst), 3);
+    }
+
+    #[tokio::test]
+    async fn test_broadcast_returns_last_reply_after_max_attempts() {
+        let (settings, attempts) = stub_node(|_| StatusCode::INTERNAL_SERVER_ERROR);
+        let reply = broadcast(&reqwest::Client::new(), &settings, "transaction")
+            .await
+            .unwrap();
+        assert_eq!(reply.status, 500);
+        assert_eq!(attempts.load(Ordering::SeqCst), 3);
+    }
+
+    #[tokio::test]
+    async fn test_broadcast_does_not_retry_rejections() {
+        let (settings, attempts) = stub_node(|_| StatusCode::BAD_REQUEST);
+        let reply = broadcast(&reqwest::Client::new(), &settings, "transaction")
+            .await
+            .unwrap();
+        assert_eq!(reply.status, 400);
+        assert_eq!(reply.attempts, 1);
+        assert_eq!(attempts.load(Ordering::SeqCst), 1);
+    }
+
+    #[test]
+    fn test_backoff_is_capped() {
+        assert_eq!(
+            next_backoff(Duration::from_millis(500)),
+            Duration::from_secs(1)
+        );
+        assert_eq!(next_backoff(Duration::from_secs(20)), MAX_BROADCAST_BACKOFF);
+        assert_eq!(next_backoff(Duration::MAX), MAX_BROADCAST_BACKOFF);
+    }
+
+    #[tokio::test]
+    async fn test_broadcast_times_out_on_hanging_node() {
+        // A node that accepts the connection but never replies.
+        let route = warp::post().and_then(|| async {
+            tokio::time::sleep(Duration::from_secs(60)).await;
+            Ok::<_, warp::Rejection>("late")
+        });
+        let (address, server) = warp::serve(route).bind_ephemeral(([127, 0, 0, 1], 0));
+        tokio::spawn(server);
+        let settings = BroadcastSettings {
+            url: format!("http://{address}/transaction/broadcast"),
+            max_attempts: 1,
+            initial_backoff: Duration::from_millis(10),
+            timeout: Duration::from_millis(100),
+        };
+        let client = reqwest::Client::builder()
+            .timeout(settings.timeout)
+            .build()
+            .unwrap();
+        let result = tokio::time::timeout(
+            Duration::from_secs(5),
+            broadcast(&client, &settings, "transaction"),
+        )
+        .await;
+        assert!(result.unwrap().is_err());
+    }
+
+    #[tokio::test]
+    async fn test_broadcast_fails_when_node_is_unreachable() {
+        let settings = BroadcastSettings {
+            url: "http://127.0.0.1:1/transaction/broadcast".to_string(),
+            max_attempts: 2,
+            initial_backoff: Duration::from_millis(10),
+            timeout: Duration::from_secs(5),
+        };
+        assert!(broadcast(&reqwest::Client::new(), &settings, "transaction")
+            .await
+            .is_err());
+    }
+}
@@ -0,0 +1,221 @@
+// Copyright (C) 2019-2024 Aleo Systems Inc.
+// This file is part of the Aleo SDK library.
+
+// The Aleo SDK library is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// The Aleo SDK library is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with the Aleo SDK library. If not, see <https://www.gnu.org/licenses/>.
+
+use super::*;
+
+use std::collections::HashMap;
+use std::future::Future;
+use std::sync::{Arc, Mutex, OnceLock};
+use std::time::{Duration, Instant};
+use tokio::sync::OnceCell;
+
+/// The results of recent executions, keyed by execution ID, so that a retried request
+/// returns the same output instead of proving the authorization again.
+pub struct ExecutionCache<T> {
+    ttl: Duration,
+    entries: Mutex<HashMap<String, CacheEntry<T>>>,
+}
+
+struct CacheEntry<T> {
+    created: Instant,
+    output: Arc<OnceCell<T>>,
+}
+
+impl<T: Clone> ExecutionCache<T> {
+    /// Initializes a cache that keeps results for `ttl`. A zero `ttl` disables caching.
+    pub fn new(ttl: Duration) -> Self {
+        Self {
+            ttl,
+            entries: Mutex::new(HashMap::new()),
+        }
+    }
+
+    /// Returns the cached output for `execution_id`, or runs `execute` to produce it.
+    /// Concurrent calls for the same execution ID wait for the first one to finish.
+    /// Failures are not cached, so a retry after a failure runs `execute` again.
+    pub async fn get_or_execute<F, Fut>(&self, execution_id: String, execute: F) -> Result<T>
+    where
+        F: FnOnce() -> Fut,
+        Fut: Future<Output = Result<T>>,
+    {
+        if self.ttl.is_zero() {
+            return execute().await;
+        }
+
+        let output = {
+            let mut entries = self.entries.lock().unwrap();
+            // Evict the expired results. Executions still in flight are kept while a request
+            // waits for them, and forgotten once every waiting request has been cancelled.
+            entries.retain(|_, entry| match entry.output.initialized() {
+                true => entry.created.elapsed() < self.ttl,
+                false => Arc::strong_count(&entry.output) > 1,
+            });
+            entries
+                .entry(execution_id.clone())
+                .or_insert_with(|| CacheEntry {
+                    created: Instant::now(),
+                    output: Arc::default(),
+                })
+                .output
+                .clone()
+        };
+
+        let result = output.get_or_try_init(execute).await.cloned();
+        if result.is_err() {
+            // Forget the failed attempt, unless a concurrent retry has since succeeded.
+            let mut entries = self.entries.lock().unwrap();
+            if let Some(entry) = entries.get(&execution_id) {
+                if Arc::ptr_eq(&entry.output, &output) && !output.initialized() {
+                    entries.remove(&execution_id);
+                }
+            }
+        }
+        result
+    }
+}
+
+/// Returns the cache of the transaction routes, configured from the settings.
+pub fn execution_cache() -> &'static ExecutionCache<ExecuteOutput> {
+    static CACHE: OnceLock<ExecutionCache<ExecuteOutput>> = OnceLock::new();
+    CACHE.get_or_init(|| ExecutionCache::new(settings().idempotency_ttl))
+}
+
+/// Returns the cache of `POST /execution`, configured from the settings.
+pub fn artifacts_cache() -> &'static ExecutionCache<ArtifactsOutput> {
+    static CACHE: OnceLock<ExecutionCache<ArtifactsOutput>> = OnceLock::new();
+    CACHE.get_or_init(|| ExecutionCache::new(settings().idempotency_ttl))
+}
+
+/// Returns the execution ID of the function authorization in a serialized `ExecuteRequest`.
+pub fn execution_id<N: Network>(request_bytes: &[u8]) -> Result<String> {
+    let request = ExecuteRequest::<N>::from_bytes_le(request_bytes)?;
+    Ok(request
+        .function_authorization
+        .to_execution_id()?
+        .to_string())
+}
+
+/// Returns the cache key of a serialized `ExecutionRequest`: the execution ID of the function
+/// authorization and, if a fee is authorized, the execution ID of the fee authorization.
+pub fn artifacts_cache_key<N: Network>(request_bytes: &[u8]) -> Result<String> {
+    let request = ExecutionRequest::<N>::from_bytes_le(request_bytes)?;
+    let execution_id = request.function_authorization.to_execution_id()?;
+    match request.fee_authorization {
+        Some(fee_authorization) => Ok(format!(
+            "{execution_id}/{}",
+            fee_authorization.to_execution_id()?
+        )),
+        None => Ok(execution_id.to_string()),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use std::sync::atomic::{AtomicU32, Ordering};
+
+    fn output(transaction: &[u8]) -> ExecuteOutput {
+        ExecuteOutput {
+            transaction: transaction.to_vec(),
+            execution: Vec::new(),
+            fee: Vec::new(),
+            timings: PhaseTimings::default(),
+        }
+    }
+
+    #[tokio::test]
+    async fn test_repeated_execution_is_cached() {
+        let cache = ExecutionCache::new(Duration::from_secs(60));
+        let runs = &AtomicU32::new(0);
+        for _ in 0..3 {
+            let result = cache
+                .get_or_execute("id".to_string(), || async move {
+                    runs.fetch_add(1, Ordering::SeqCst);
+                    Ok(output(b"transaction"))
+                })
+                .await
+                .unwrap();
+            assert_eq!(result.transaction, b"transaction");
+        }
+        assert_eq!(runs.load(Ordering::SeqCst), 1);
+    }
+
+    #[tokio::test]
+    async fn test_concurrent_execution_attaches_to_running_job() {
+        let cache = ExecutionCache::new(Duration::from_secs(60));
+        let runs = &AtomicU32::new(0);
+        let execute = || async move {
+            runs.fetch_add(1, Ordering::SeqCst);
+            tokio::time::sleep(Duration::from_millis(50)).await;
+            Ok(output(b"transaction"))
+        };
+        let (first, second) = tokio::join!(
+            cache.get_or_execute("id".to_string(), execute),
+            cache.get_or_execute("id".to_string(), execute),
+        );
+        assert_eq!(first.unwrap().transaction, second.unwrap().transaction);
+        assert_eq!(runs.load(Ordering::SeqCst), 1);
+    }
+
+    #[tokio::test]
+    async fn test_failures_are_not_cached() {
+        let cache = ExecutionCache::new(Duration::from_secs(60));
+        let result = cache
+            .get_or_execute("id".to_string(), || async { Err(anyhow!("failed")) })
+            .await;
+        assert!(result.is_err());
+        let result = cache
+            .get_or_execute("id".to_string(), || async { Ok(output(b"retried")) })
+            .await;
+        assert_eq!(result.unwrap().transaction, b"retried");
+    }
+
+    #[tokio::test]
+    async fn test_expired_results_are_recomputed() {
+        let cache = ExecutionCache::new(Duration::from_millis(10));
+        let runs = &AtomicU32::new(0);
+        for _ in 0..2 {
+            cache
+                .get_or_execute("id".to_string(), || async move {
+                    runs.fetch_add(1, Ordering::SeqCst);
+                    Ok(output(b"transaction"))
+                })
+                .await
+                .unwrap();
+            tokio::time::sleep(Duration::from_millis(20)).await;
+        }
+        assert_eq!(runs.load(Ordering::SeqCst), 2);
+    }
+
+    #[tokio::test]
+    async fn test_cancelled_executions_are_evicted() {
+        let cache = ExecutionCache::new(Duration::from_secs(60));
+        let cancelled = tokio::time::timeout(
+            Duration::from_millis(10),
+            cache.get_or_execute("cancelled".to_string(), || std::future::pending()),
+        )
+        .await;
+        assert!(cancelled.is_err());
+        cache
+            .get_or_execute("id".to_string(), || async { Ok(output(b"transaction")) })
+            .await
+            .unwrap();
+        let entries = cache.entries.lock().unwrap();
+        assert!(!entries.contains_key("cancelled"));
+        assert!(entries.contains_key("id"));
+    }
+}
@@ -0,0 +1,232 @@
+// Copyright (C) 2019-2024 Aleo Systems Inc.
+// This file is part of the Aleo SDK library.
+
+// The Aleo SDK library is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// The Aleo SDK library is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with the Aleo SDK library. If not, see <https://www.gnu.org/licenses/>.
+
+use super::*;
+
+use std::net::IpAddr;
+use std::time::Duration;
+
+/// The `[execute]` section of the configuration file.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct ExecuteConfig {
+    /// The address the HTTP and gRPC servers bind to.
+    pub bind: IpAddr,
+    /// The port the HTTP server listens on.
+    pub port: u16,
+    /// The port of the gRPC server, if it is enabled.
+    pub grpc_port: Option<u16>,
+    /// The maximum request body size in bytes.
+    pub body_limit: u64,
+    /// The maximum body size in bytes of a batch request.
+    pub batch_body_limit: u64,
+    /// The maximum number of items in a batch.
+    pub batch_max_items: usize,
+    /// The number of worker threads. Defaults to one per CPU.
+    pub threads: Option<usize>,
+    /// The programs or functions whose proving keys are loaded at startup, within `credits.aleo`.
+    pub preload: Vec<String>,
+    /// Whether the function and the fee are proved at the same time: `parallel` or `sequential`.
+    pub proving: ProvingMode,
+    /// Whether each transaction is verified before it is returned.
+    pub verify: bool,
+    /// The node endpoint finished transactions are forwarded to.
+    pub broadcast_url: Option<String>,
+    /// The maximum number of broadcast attempts per transaction.
+    pub broadcast_attempts: u32,
+    /// The delay in milliseconds before the first broadcast retry.
+    pub broadcast_backoff_ms: u64,
+    /// The time in seconds allowed for each broadcast attempt.
+    pub broadcast_timeout_secs: u64,
+    /// How long, in seconds, a result is returned again for the same execution ID.
+    pub idempotency_ttl_secs: u64,
+    /// How long, in seconds, to wait for in-flight jobs on shutdown.
+    pub shutdown_timeout_secs: u64,
+}
+
+impl Default for ExecuteConfig {
+    fn default() -> Self {
+        Self {
+            bind: IpAddr::from([0, 0, 0, 0]),
+            port: 8081,
+            grpc_port: None,
+            body_limit: 32 * 1024,             // 32 KiB
+            batch_body_limit: 4 * 1024 * 1024, // 4 MiB
+            batch_max_items: 128,
+            threads: None,
+            preload: vec!["credits.aleo".to_string()],
+            proving: ProvingMode::default(),
+            verify: false,
+            broadcast_url: None,
+            broadcast_attempts: 5,
+            broadcast_backoff_ms: 500,
+            broadcast_timeout_secs: 30,
+            idempotency_ttl_secs: 600,
+            shutdown_timeout_secs: 300,
+        }
+    }
+}
+
+impl ServiceConfig for ExecuteConfig {
+    const SECTION: &'static str = "execute";
+
+    fn apply_env(&mut self) -> Result<()> {
+        env_value("ALEO_EXECUTE_BIND", &mut self.bind)?;
+        env_value("ALEO_EXECUTE_PORT", &mut self.port)?;
+        env_option("ALEO_EXECUTE_GRPC_PORT", &mut self.grpc_port)?;
+        env_value("ALEO_EXECUTE_BODY_LIMIT", &mut self.body_limit)?;
+        env_value("ALEO_EXECUTE_BATCH_BODY_LIMIT", &mut self.batch_body_limit)?;
+        env_value("ALEO_EXECUTE_BATCH_MAX_ITEMS", &mut self.batch_max_items)?;
+        env_option("ALEO_EXECUTE_THREADS", &mut self.threads)?;
+        if let Ok(preload) = std::env::var("ALEO_EXECUTE_PRELOAD") {
+            self.preload = preload.split(',').map(str::to_string).collect();
+        }
+        env_value("ALEO_EXECUTE_PROVING", &mut self.proving)?;
+        env_value("ALEO_EXECUTE_VERIFY", &mut self.verify)?;
+        env_option("ALEO_EXECUTE_BROADCAST_URL", &mut self.broadcast_url)?;
+        env_value(
+            "ALEO_EXECUTE_BROADCAST_ATTEMPTS",
+            &mut self.broadcast_attempts,
+        )?;
+        env_value(
+            "ALEO_EXECUTE_BROADCAST_BACKOFF_MS",
+            &mut self.broadcast_backoff_ms,
+        )?;
+        env_value(
+            "ALEO_EXECUTE_BROADCAST_TIMEOUT_SECS",
+            &mut self.broadcast_timeout_secs,
+        )?;
+        env_value(
+            "ALEO_EXECUTE_IDEMPOTENCY_TTL_SECS",
+            &mut self.idempotency_ttl_secs,
+        )?;
+        env_value(
+            "ALEO_EXECUTE_SHUTDOWN_TIMEOUT_SECS",
+            &mut self.shutdown_timeout_secs,
+        )?;
+        Ok(())
+    }
+
+    fn validate(&self) -> Result<()> {
+        ensure!(self.port != 0, "The execute port must not be 0");
+        ensure!(
+            self.grpc_port != Some(self.port),
+            "The execute gRPC port must differ from the HTTP port"
+        );
+        ensure!(self.body_limit > 0, "The execute body limit must not be 0");
+        ensure!(
+            self.batch_body_limit >= self.body_limit,
+            "The execute batch body limit must be at least the body limit"
+        );
+        ensure!(
+            self.batch_max_items > 0,
+            "The execute batch max items must not be 0"
+        );
+        ensure!(
+            self.threads != Some(0),
+            "The execute thread count must not be 0"
+        );
+        ensure!(
+            self.broadcast_attempts > 0,
+            "The execute broadcast attempts must not be 0"
+        );
+        ensure!(
+            self.broadcast_timeout_secs > 0,
+            "The execute broadcast timeout must not be 0"
+        );
+        if let Some(url) = &self.broadcast_url {
+            reqwest::Url::parse(url)
+                .map_err(|error| anyhow!("Invalid broadcast URL '{url}': {error}"))?;
+        }
+        for target in self.preload_targets()? {
+            target.check_resident()?;
+        }
+        Ok(())
+    }
+}
+
+impl ExecuteConfig {
+    /// Returns the parsed preload targets.
+    pub fn preload_targets(&self) -> Result<Vec<PreloadTarget>> {
+        self.preload.iter().map(|target| target.parse()).collect()
+    }
+
+    /// Returns the settings shared by the worker threads.
+    pub fn settings(&self) -> Result<Settings> {
+        Ok(Settings {
+            preload: self.preload_targets()?,
+            proving: self.proving,
+            verify: self.verify,
+            broadcast: self.broadcast_url.clone().map(|url| BroadcastSettings {
+                url,
+                max_attempts: self.broadcast_attempts,
+                initial_backoff: Duration::from_millis(self.broadcast_backoff_ms),
+                timeout: Duration::from_secs(self.broadcast_timeout_secs),
+            }),
+            idempotency_ttl: Duration::from_secs(self.idempotency_ttl_secs),
+            body_limit: self.body_limit,
+            batch_body_limit: self.batch_body_limit,
+            batch_max_items: self.batch_max_items,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_default_config_is_valid() {
+        assert!(ExecuteConfig::default().validate().is_ok());
+    }
+
+    #[test]
+    fn test_validate_rejects_invalid_values() {
+        let invalid = [
+            ExecuteConfig {
+                port: 0,
+                ..Default::default()
+            },
+            ExecuteConfig {
+                grpc_port: Some(8081),
+                ..Default::default()
+            },
+            ExecuteConfig {
+                batch_body_limit: 1024,
+                ..Default::default()
+            },
+            ExecuteConfig {
+                broadcast_timeout_secs: 0,
+                ..Default::default()
+            },
+            ExecuteConfig {
+                broadcast_url: Some("not a url".to_string()),
+                ..Default::default()
+            },
+            ExecuteConfig {
+                preload: vec!["credits.aleo/".to_string()],
+                ..Default::default()
+            },
+            ExecuteConfig {
+                preload: vec!["token.aleo".to_string()],
+                ..Default::default()
+            },
+        ];
+        for config in invalid {
+            assert!(config.validate().is_err(), "{config:?}");
+        }
+    }
+}
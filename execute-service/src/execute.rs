@@ -16,15 +16,71 @@
 
 use super::*;
 
-use std::cell::RefCell;
+use std::cell::{Cell, RefCell};
 use warp::hyper::body::Bytes;
 
 // Initialize a thread-local `ProcessVariant`.
 thread_local! {
     pub static PROCESS: RefCell<Option<ProcessVariant>> = const { RefCell::new(None) };
+    // The value of `key_loads` when this worker last recorded its resident proving keys.
+    static RECORDED_KEY_LOADS: Cell<Option<usize>> = const { Cell::new(None) };
 }
 
-pub fn execute<N: Network>(bytes: Bytes) -> Result<Vec<u8>> {
+/// A serialized transaction, along with the time spent in each phase of producing it.
+/// The execution and the fee are also kept serialized on their own, for `?output=artifacts`.
+#[derive(Clone, Debug)]
+pub struct ExecuteOutput {
+    pub transaction: Vec<u8>,
+    pub execution: Vec<u8>,
+    pub fee: Vec<u8>,
+    pub timings: PhaseTimings,
+}
+
+/// A serialized execution and, if one was authorized, a serialized fee, proved separately
+/// so that the transaction can be assembled elsewhere.
+#[derive(Clone, Debug)]
+pub struct ArtifactsOutput {
+    pub execution: Vec<u8>,
+    pub fee: Option<Vec<u8>>,
+    pub timings: PhaseTimings,
+}
+
+pub fn execute<N: Network>(bytes: Bytes) -> Result<ExecuteOutput> {
+    execute_with_progress::<N>(bytes, &|_| {})
+}
+
+/// Computes the transaction, proving the function and the fee as `proving` selects
+/// rather than as the settings do.
+pub fn execute_with_proving<N: Network>(
+    bytes: Bytes,
+    proving: ProvingMode,
+) -> Result<ExecuteOutput> {
+    with_process::<N, _>(|process| process.execute(&bytes, proving, &|_| {}))
+}
+
+/// Computes the transaction, calling `on_phase` as each phase of the execution completes.
+pub fn execute_with_progress<N: Network>(
+    bytes: Bytes,
+    on_phase: &(dyn Fn(Phase) + Sync),
+) -> Result<ExecuteOutput> {
+    // Compute the `Execution`.
+    with_process::<N, _>(|process| process.execute(&bytes, settings().proving, on_phase))
+}
+
+/// Computes the execution of an `ExecutionRequest`, along with its fee if one is authorized.
+pub fn execute_artifacts<N: Network>(bytes: Bytes) -> Result<ArtifactsOutput> {
+    with_process::<N, _>(|process| process.execute_artifacts(&bytes, settings().proving, &|_| {}))
+}
+
+/// Loads the process and the preloaded proving keys on every worker of the global rayon pool.
+pub fn warm_up_workers<N: Network>() -> Result<()> {
+    rayon::broadcast(|_| with_process::<N, _>(|_| Ok(())))
+        .into_iter()
+        .collect()
+}
+
+/// Runs `f` against the thread-local process, initializing it if necessary.
+fn with_process<N: Network, T>(f: impl FnOnce(&ProcessVariant) -> Result<T>) -> Result<T> {
     PROCESS.with(|process| {
         // Initialize the process if it is not already initialized.
         if process.borrow().is_none() {
@@ -34,21 +90,31 @@ pub fn execute<N: Network>(bytes: Bytes) -> Result<Vec<u8>> {
                 CanaryV0::ID => Some(ProcessVariant::CanaryV0(load_process::<CanaryV0>()?)),
                 _ => panic!("Invalid network"),
             };
+            metrics().loaded_processes.inc();
         };
-        // Compute the `Execution`.
-        process.borrow().as_ref().unwrap().execute(&bytes)
+        let process = process.borrow();
+        let process = process.as_ref().unwrap();
+        let result = f(process);
+        // Record the proving keys held by this worker once it loads, and again after any key load.
+        let loads = key_loads();
+        if RECORDED_KEY_LOADS.with(Cell::get) != Some(loads) {
+            match process.resident_proving_keys(&settings().preload) {
+                Ok(locators) => {
+                    record_resident_keys(locators);
+                    RECORDED_KEY_LOADS.with(|recorded| recorded.set(Some(loads)));
+                }
+                Err(error) => log::warn!("Failed to record the resident proving keys: {error}"),
+            }
+        }
+        result
     })
 }
 
-/// A helper function to load a Process and the necessary proving keys.
+/// A helper function to load a Process and the proving keys selected for preloading.
 pub fn load_process<N: Network>() -> Result<Process<N>> {
     // Load the process.
     let process = Process::load()?;
-    // Initialize the proving keys for the functions in credits.aleo.
-    let credits_program = process.get_program("credits.aleo")?;
-    for (function_name, _) in credits_program.functions() {
-        // Get the proving key. This method will load the proving key if it does not exist.
-        let _ = process.get_proving_key("credits.aleo", function_name)?;
-    }
+    // Initialize the proving keys for the preloaded programs and functions.
+    preload_proving_keys(&process, &settings().preload)?;
     Ok(process)
 }
@@ -0,0 +1,202 @@
+// Copyright (C) 2019-2024 Aleo Systems Inc.
+// This file is part of the Aleo SDK library.
+
+// The Aleo SDK library is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// The Aleo SDK library is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with the Aleo SDK library. If not, see <https://www.gnu.org/licenses/>.
+
+use super::*;
+
+use std::future::Future;
+use std::marker::PhantomData;
+use std::net::SocketAddr;
+use tokio::net::TcpListener;
+use tokio_stream::wrappers::TcpListenerStream;
+use tonic::{Request, Response, Status};
+use warp::hyper::body::Bytes;
+
+pub mod proto {
+    tonic::include_proto!("execute");
+}
+
+use proto::execute_service_server::{ExecuteService, ExecuteServiceServer};
+
+/// The room a gRPC message leaves for the protobuf framing around the request bytes, so that a
+/// request at the body limit is accepted as it is over HTTP.
+const MESSAGE_OVERHEAD: usize = 16;
+
+/// The gRPC interface of the execute service, backed by the same handlers as the HTTP routes.
+pub struct GrpcService<N: Network> {
+    client: reqwest::Client,
+    _network: PhantomData<N>,
+}
+
+impl<N: Network> Default for GrpcService<N> {
+    fn default() -> Self {
+        Self {
+            client: broadcast_client(),
+            _network: PhantomData,
+        }
+    }
+}
+
+#[tonic::async_trait]
+impl<N: Network> ExecuteService for GrpcService<N> {
+    async fn execute(
+        &self,
+        request: Request<proto::ExecuteRequest>,
+    ) -> Result<Response<proto::ExecuteResponse>, Status> {
+        if is_draining() {
+            return Err(Status::unavailable("The service is shutting down"));
+        }
+        // The message limit leaves room for the framing, so check the request bytes themselves.
+        let request_bytes = Bytes::from(request.into_inner().request);
+        if request_bytes.len() as u64 > settings().body_limit {
+            return Err(Status::out_of_range(format!(
+                "The request exceeds the limit of {} bytes",
+                settings().body_limit
+            )));
+        }
+        let output = match execute_once::<N>(request_bytes).await {
+            Ok(output) => output,
+            Err(error) => match error.downcast_ref::<VerificationError>() {
+                Some(error) => {
+                    metrics().record_request("grpc_execute", "verification_failed");
+                    return Err(Status::failed_precondition(error.to_string()));
+                }
+                None => {
+                    metrics().record_request("grpc_execute", "error");
+                    return Err(Status::internal(error.to_string()));
+                }
+            },
+        };
+        metrics().record_request("grpc_execute", "success");
+
+        // Forward the transaction to the node, if broadcasting is enabled.
+        let broadcast = match &settings().broadcast {
+            Some(broadcast_settings) => Some(
+                self.forward(broadcast_settings, &output.transaction)
+                    .await?,
+            ),
+            None => None,
+        };
+
+        Ok(Response::new(proto::ExecuteResponse {
+            phase_timings_ms: output
+                .timings
+                .phases()
+                .iter()
+                .map(|(phase, duration)| {
+                    (phase.name().to_string(), duration.as_secs_f64() * 1000.0)
+                })
+                .collect(),
+            transaction: output.transaction,
+            broadcast,
+        }))
+    }
+}
+
+impl<N: Network> GrpcService<N> {
+    /// Forwards the serialized transaction to the node and reports the node's reply.
+    async fn forward(
+        &self,
+        broadcast_settings: &BroadcastSettings,
+        transaction_bytes: &[u8],
+    ) -> Result<proto::Broadcast, Status> {
+        let transaction = Transaction::<N>::from_bytes_le(transaction_bytes)
+            .map_err(|error| Status::internal(error.to_string()))?;
+        let reply = broadcast(&self.client, broadcast_settings, &transaction)
+            .await
+            .map_err(|error| Status::unavailable(error.to_string()))?;
+        Ok(proto::Broadcast {
+            transaction_id: transaction.id().to_string(),
+            node_status: reply.status.into(),
+            node_response: reply.body,
+            attempts: reply.attempts,
+        })
+    }
+}
+
+/// Binds the gRPC interface to `address`, returning the bound address and the server.
+/// The server stops accepting requests once the service starts shutting down.
+pub async fn bind_grpc<N: Network>(
+    address: SocketAddr,
+) -> Result<(SocketAddr, impl Future<Output = Result<()>>)> {
+    let listener = TcpListener::bind(address).await?;
+    let address = listener.local_addr()?;
+    let service = ExecuteServiceServer::new(GrpcService::<N>::default())
+        .max_decoding_message_size(settings().body_limit as usize + MESSAGE_OVERHEAD);
+    let server = async move {
+        tonic::transport::Server::builder()
+            .add_service(service)
+            .serve_with_incoming_shutdown(TcpListenerStream::new(listener), wait_for_draining())
+            .await?;
+        Ok(())
+    };
+    Ok((address, server))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use proto::execute_service_client::ExecuteServiceClient;
+    use tonic::Code;
+
+    async fn spawn_grpc() -> ExecuteServiceClient<tonic::transport::Channel> {
+        let (address, server) = bind_grpc::<TestnetV0>(([127, 0, 0, 1], 0).into())
+            .await
+            .unwrap();
+        tokio::spawn(server);
+        ExecuteServiceClient::connect(format!("http://{address}"))
+            .await
+            .unwrap()
+    }
+
+    #[tokio::test]
+    async fn test_grpc_rejects_invalid_request() {
+        let mut client = spawn_grpc().await;
+        let request = proto::ExecuteRequest {
+            request: vec![1, 2, 3],
+        };
+        let status = client.execute(request).await.unwrap_err();
+        assert_eq!(status.code(), Code::Internal);
+    }
+
+    #[tokio::test]
+    async fn test_grpc_accepts_request_at_body_limit() {
+        let mut client = spawn_grpc().await;
+        let request = proto::ExecuteRequest {
+            request: vec![0; settings().body_limit as usize],
+        };
+        // The request passes the size check and fails to decode instead.
+        let status = client.execute(request).await.unwrap_err();
+        assert_eq!(status.code(), Code::Internal);
+    }
+
+    #[tokio::test]
+    async fn test_grpc_rejects_request_over_body_limit() {
+        let mut client = spawn_grpc().await;
+        let request = proto::ExecuteRequest {
+            request: vec![0; settings().body_limit as usize + 1],
+        };
+        let status = client.execute(request).await.unwrap_err();
+        assert_eq!(status.code(), Code::OutOfRange);
+    }
+
+    #[tokio::test]
+    async fn test_grpc_bind_fails_on_address_in_use() {
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let address = listener.local_addr().unwrap();
+        assert!(bind_grpc::<TestnetV0>(address).await.is_err());
+    }
+}
@@ -0,0 +1,45 @@
+// Copyright (C) 2019-2024 Aleo Systems Inc.
+// This file is part of the Aleo SDK library.
+
+// The Aleo SDK library is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// The Aleo SDK library is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with the Aleo SDK library. If not, see <https://www.gnu.org/licenses/>.
+
+use super::*;
+
+/// Reports whether every worker has loaded its process and preloaded proving keys.
+/// A worker is only recorded once its process has loaded, so a missing worker is still warming up.
+pub fn readiness<N: Network>() -> ReadinessResponse {
+    let resident = resident_keys();
+    let workers = (0..rayon::current_num_threads())
+        .map(|worker| {
+            let keys = resident.workers.iter().find(|keys| keys.worker == worker);
+            WorkerReadiness {
+                worker,
+                process_loaded: keys.is_some(),
+                proving_keys: keys
+                    .map(|keys| keys.proving_keys.clone())
+                    .unwrap_or_default(),
+            }
+        })
+        .collect::<Vec<_>>();
+    let draining = is_draining();
+    ReadinessResponse {
+        ready: !draining && workers.iter().all(|worker| worker.process_loaded),
+        draining,
+        network: N::NAME.to_string(),
+        version: env!("CARGO_PKG_VERSION").to_string(),
+        queue_depth: metrics().queue_depth.get(),
+        preload: settings().preload.iter().map(ToString::to_string).collect(),
+        workers,
+    }
+}
@@ -0,0 +1,234 @@
+// Copyright (C) 2019-2024 Aleo Systems Inc.
+// This file is part of the Aleo SDK library.
+
+// The Aleo SDK library is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// The Aleo SDK library is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with the Aleo SDK library. If not, see <https://www.gnu.org/licenses/>.
+
+use super::*;
+
+use snarkvm::prelude::{Identifier, ProgramID};
+use std::collections::{BTreeMap, BTreeSet};
+use std::fmt;
+use std::str::FromStr;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Mutex;
+
+/// The programs held by the process. Only their proving keys can be loaded.
+pub const RESIDENT_PROGRAMS: &[&str] = &["credits.aleo"];
+
+/// A program, or a single function of a program, whose proving keys are loaded at startup.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct PreloadTarget {
+    pub program_id: String,
+    pub function_name: Option<String>,
+}
+
+impl PreloadTarget {
+    /// Returns a target covering every function of the given program.
+    pub fn program(program_id: &str) -> Self {
+        Self {
+            program_id: program_id.to_string(),
+            function_name: None,
+        }
+    }
+
+    /// Checks that the process holds the program of the target.
+    pub fn check_resident(&self) -> Result<()> {
+        ensure!(
+            RESIDENT_PROGRAMS.contains(&self.program_id.as_str()),
+            "Cannot preload '{self}': only the proving keys of {} can be loaded",
+            RESIDENT_PROGRAMS.join(", ")
+        );
+        Ok(())
+    }
+}
+
+impl FromStr for PreloadTarget {
+    type Err = anyhow::Error;
+
+    /// Parses either `program.aleo` or `program.aleo/function_name`.
+    fn from_str(target: &str) -> Result<Self> {
+        let (program_id, function_name) = match target.trim().split_once('/') {
+            Some((program_id, function_name)) => (program_id, Some(function_name)),
+            None => (target.trim(), None),
+        };
+        ensure!(
+            program_id.ends_with(".aleo"),
+            "Invalid program ID '{program_id}' in preload target '{target}'"
+        );
+        ensure!(
+            function_name != Some(""),
+            "Missing function name in preload target '{target}'"
+        );
+        Ok(Self {
+            program_id: program_id.to_string(),
+            function_name: function_name.map(str::to_string),
+        })
+    }
+}
+
+impl fmt::Display for PreloadTarget {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match &self.function_name {
+            Some(function_name) => write!(f, "{}/{}", self.program_id, function_name),
+            None => write!(f, "{}", self.program_id),
+        }
+    }
+}
+
+/// Loads the proving keys for the given targets into the process.
+pub fn preload_proving_keys<N: Network>(
+    process: &Process<N>,
+    targets: &[PreloadTarget],
+) -> Result<()> {
+    for target in targets {
+        let program_id = ProgramID::<N>::from_str(&target.program_id)?;
+        let function_names = match &target.function_name {
+            Some(function_name) => vec![Identifier::from_str(function_name)?],
+            None => process
+                .get_program(program_id)?
+                .functions()
+                .keys()
+                .copied()
+                .collect(),
+        };
+        for function_name in function_names {
+            load_proving_key(process, &Locator::new(program_id, function_name))?;
+        }
+    }
+    Ok(())
+}
+
+/// Loads the proving key for the function at `locator`, timing the load if the key is not resident.
+pub fn load_proving_key<N: Network>(process: &Process<N>, locator: &Locator<N>) -> Result<()> {
+    let stack = process.get_stack(*locator.program_id())?;
+    if !stack.contains_proving_key(locator.resource()) {
+        let _timer = metrics()
+            .key_load_duration
+            .with_label_values(&[
+                &locator.program_id().to_string(),
+                &locator.resource().to_string(),
+            ])
+            .start_timer();
+        // Get the proving key. This method will load the proving key if it does not exist.
+        let _ = process.get_proving_key(*locator.program_id(), *locator.resource())?;
+        KEY_LOADS.fetch_add(1, Ordering::SeqCst);
+    }
+    Ok(())
+}
+
+// The number of proving keys loaded by any worker, so that workers rescan only after a load.
+static KEY_LOADS: AtomicUsize = AtomicUsize::new(0);
+
+/// Returns the number of proving keys loaded since startup.
+pub fn key_loads() -> usize {
+    KEY_LOADS.load(Ordering::SeqCst)
+}
+
+/// Returns the locators of the functions whose proving keys are held by the process.
+/// Only `credits.aleo` and the programs named in `targets` are inspected, skipping those the
+/// process does not hold.
+pub fn resident_proving_keys<N: Network>(
+    process: &Process<N>,
+    targets: &[PreloadTarget],
+) -> Result<BTreeSet<String>> {
+    let mut program_ids = targets
+        .iter()
+        .map(|target| target.program_id.as_str())
+        .collect::<BTreeSet<_>>();
+    program_ids.insert("credits.aleo");
+
+    let mut locators = BTreeSet::new();
+    for program_id in program_ids {
+        let Ok(stack) = process.get_stack(program_id) else {
+            continue;
+        };
+        for function_name in stack.program().functions().keys() {
+            if stack.contains_proving_key(function_name) {
+                locators.insert(Locator::new(*stack.program_id(), *function_name).to_string());
+            }
+        }
+    }
+    Ok(locators)
+}
+
+// The proving keys resident on each worker thread, keyed by the rayon thread index.
+static RESIDENT_KEYS: Mutex<BTreeMap<usize, BTreeSet<String>>> = Mutex::new(BTreeMap::new());
+
+/// Records the proving keys held by the calling worker thread.
+/// Calls made outside of the rayon pool are ignored.
+pub fn record_resident_keys(locators: BTreeSet<String>) {
+    if let Some(worker) = rayon::current_thread_index() {
+        RESIDENT_KEYS.lock().unwrap().insert(worker, locators);
+    }
+}
+
+/// Returns the proving keys most recently recorded by each worker thread.
+pub fn resident_keys() -> KeysResponse {
+    let workers = RESIDENT_KEYS
+        .lock()
+        .unwrap()
+        .iter()
+        .map(|(worker, locators)| WorkerKeys {
+            worker: *worker,
+            proving_keys: locators.iter().cloned().collect(),
+        })
+        .collect();
+    KeysResponse { workers }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_preload_targets() {
+        assert_eq!(
+            PreloadTarget::from_str("credits.aleo").unwrap(),
+            PreloadTarget::program("credits.aleo")
+        );
+        assert_eq!(
+            PreloadTarget::from_str(" credits.aleo/transfer_public ").unwrap(),
+            PreloadTarget {
+                program_id: "credits.aleo".to_string(),
+                function_name: Some("transfer_public".to_string()),
+            }
+        );
+        for target in ["credits.aleo/transfer_public", "token.aleo"] {
+            assert_eq!(PreloadTarget::from_str(target).unwrap().to_string(), target);
+        }
+    }
+
+    #[test]
+    fn test_check_resident_preload_targets() {
+        PreloadTarget::from_str("credits.aleo/transfer_public")
+            .unwrap()
+            .check_resident()
+            .unwrap();
+        let error = PreloadTarget::from_str("token.aleo")
+            .unwrap()
+            .check_resident()
+            .unwrap_err();
+        assert!(error
+            .to_string()
+            .contains("only the proving keys of credits.aleo"));
+    }
+
+    #[test]
+    fn test_reject_invalid_preload_targets() {
+        assert!(PreloadTarget::from_str("credits").is_err());
+        assert!(PreloadTarget::from_str("credits/transfer_public").is_err());
+        assert!(PreloadTarget::from_str("credits.aleo/").is_err());
+        assert!(PreloadTarget::from_str("").is_err());
+    }
+}
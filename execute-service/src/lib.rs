@@ -14,9 +14,32 @@
 // You should have received a copy of the GNU General Public License
 // along with the Aleo SDK library. If not, see <https://www.gnu.org/licenses/>.
 
+pub mod broadcast;
+pub use broadcast::*;
+
+pub mod cache;
+pub use cache::*;
+
+pub mod config;
+pub use config::*;
+
 pub mod execute;
 pub use execute::*;
 
+#[cfg(feature = "grpc")]
+pub mod grpc;
+#[cfg(feature = "grpc")]
+pub use grpc::*;
+
+pub mod health;
+pub use health::*;
+
+pub mod keys;
+pub use keys::*;
+
+pub mod metrics;
+pub use metrics::*;
+
 pub mod process_variant;
 pub use process_variant::*;
 
@@ -26,15 +49,31 @@ pub use query::*;
 pub mod request;
 pub use request::*;
 
+pub mod response;
+pub use response::*;
+
 pub mod routes;
 pub use routes::*;
 
+pub mod settings;
+pub use settings::*;
+
+pub use service_common::config::*;
+pub use service_common::metrics::*;
+pub use service_common::shutdown::*;
+
+pub mod timings;
+pub use timings::*;
+
+pub mod verify;
+pub use verify::*;
+
 use snarkvm::circuit::{Aleo, AleoCanaryV0, AleoTestnetV0, AleoV0};
-use snarkvm::ledger::block::Transaction;
+use snarkvm::ledger::block::{Execution, Fee, Transaction};
 use snarkvm::prelude::{
-    Authorization, CanaryV0, FromBytes, Locator, MainnetV0, Network, Process, StatePath, TestnetV0,
-    ToBytes,
+    execution_cost, Authorization, CanaryV0, Deserialize, FromBytes, Locator, MainnetV0, Network,
+    Process, Serialize, StatePath, TestnetV0, ToBytes,
 };
 
-use anyhow::{anyhow, Result};
+use anyhow::{anyhow, ensure, Result};
 use rand_chacha::rand_core::SeedableRng;
@@ -17,51 +17,234 @@
 use execute_service::*;
 
 use snarkvm::prelude::{CanaryV0, MainnetV0, Network, TestnetV0};
+use std::net::SocketAddr;
+use std::path::PathBuf;
+use std::time::Duration;
 use structopt::StructOpt;
 use warp::Filter;
 
+/// Flags override the `ALEO_*` environment variables, which override the configuration file.
 #[derive(StructOpt, Debug)]
 struct Opt {
+    /// The TOML configuration file shared by the authorize and execute services.
+    #[structopt(short, long, parse(from_os_str))]
+    config: Option<PathBuf>,
+    /// Print the effective configuration and exit.
+    #[structopt(long)]
+    print_config: bool,
     #[structopt(short, long)]
-    network: String,
-    #[structopt(short, long, default_value = "8081")]
-    port: u16,
+    network: Option<String>,
+    #[structopt(short, long)]
+    port: Option<u16>,
+    /// The programs or functions whose proving keys are loaded at startup,
+    /// e.g. `credits.aleo` or `credits.aleo/transfer_public`. Only `credits.aleo` is supported.
+    /// All other keys are loaded on first use.
+    #[structopt(long, use_delimiter = true)]
+    preload: Option<Vec<String>>,
+    /// Verify each transaction against the verifying keys before returning it.
+    #[structopt(long)]
+    verify: bool,
+    /// Forward each transaction to this node endpoint, e.g. `http://localhost:3030/mainnet/transaction/broadcast`.
+    #[structopt(long)]
+    broadcast_url: Option<String>,
+    /// The maximum number of broadcast attempts per transaction.
+    #[structopt(long)]
+    broadcast_attempts: Option<u32>,
+    /// The delay in milliseconds before the first broadcast retry, doubled after each failure.
+    #[structopt(long)]
+    broadcast_backoff_ms: Option<u64>,
+    /// The time in seconds allowed for each broadcast attempt.
+    #[structopt(long)]
+    broadcast_timeout_secs: Option<u64>,
+    /// How long, in seconds, a result is returned again for the same execution ID. 0 disables this.
+    #[structopt(long)]
+    idempotency_ttl_secs: Option<u64>,
+    /// Serve the gRPC interface on this port. Requires the `grpc` feature.
+    #[structopt(long)]
+    grpc_port: Option<u16>,
+    /// How long, in seconds, to wait for in-flight jobs after SIGINT or SIGTERM.
+    #[structopt(long)]
+    shutdown_timeout_secs: Option<u64>,
+}
+
+impl Opt {
+    // Applies the flags that were given on top of the configuration.
+    fn apply(self, config: &mut Config<ExecuteConfig>) {
+        let execute = &mut config.service;
+        config.network = self.network.or(config.network.take());
+        execute.port = self.port.unwrap_or(execute.port);
+        execute.preload = self.preload.unwrap_or(std::mem::take(&mut execute.preload));
+        execute.verify |= self.verify;
+        execute.broadcast_url = self.broadcast_url.or(execute.broadcast_url.take());
+        execute.broadcast_attempts = self
+            .broadcast_attempts
+            .unwrap_or(execute.broadcast_attempts);
+        execute.broadcast_backoff_ms = self
+            .broadcast_backoff_ms
+            .unwrap_or(execute.broadcast_backoff_ms);
+        execute.broadcast_timeout_secs = self
+            .broadcast_timeout_secs
+            .unwrap_or(execute.broadcast_timeout_secs);
+        execute.idempotency_ttl_secs = self
+            .idempotency_ttl_secs
+            .unwrap_or(execute.idempotency_ttl_secs);
+        execute.grpc_port = self.grpc_port.or(execute.grpc_port);
+        execute.shutdown_timeout_secs = self
+            .shutdown_timeout_secs
+            .unwrap_or(execute.shutdown_timeout_secs);
+    }
 }
 
-async fn run<N: Network>(port: u16) {
-    pretty_env_logger::init();
+async fn run<N: Network>(config: ExecuteConfig) {
+    // Load the process and proving keys on every worker in the background.
+    // `/health/ready` reports ready once they are in memory.
+    tokio::task::spawn_blocking(|| {
+        if let Err(error) = warm_up_workers::<N>() {
+            log::error!("Failed to load the {} process: {error}", N::NAME);
+            std::process::exit(1);
+        }
+    });
+
+    // Serve the gRPC interface alongside the HTTP routes, if requested.
+    #[cfg(feature = "grpc")]
+    let grpc = match config.grpc_port {
+        Some(grpc_port) => match bind_grpc::<N>(SocketAddr::new(config.bind, grpc_port)).await {
+            Ok((_, server)) => Some(tokio::spawn(server)),
+            Err(error) => {
+                log::error!("Failed to serve the gRPC interface on port {grpc_port}: {error}");
+                std::process::exit(1);
+            }
+        },
+        None => None,
+    };
+    #[cfg(not(feature = "grpc"))]
+    if let Some(grpc_port) = config.grpc_port {
+        log::warn!("Ignoring --grpc-port {grpc_port}: built without the `grpc` feature");
+    }
 
     let routes = execute_route::<N>()
         .with(warp::trace(
             |info| tracing::debug_span!("Debugging headers", headers = ?info.request_headers()),
         ))
+        .or(execution_route::<N>().with(warp::trace(
+            |info| tracing::debug_span!("Debugging headers", headers = ?info.request_headers()),
+        )))
+        .or(execute_batch_route::<N>().with(warp::trace(
+            |info| tracing::debug_span!("Debugging headers", headers = ?info.request_headers()),
+        )))
+        .or(execute_stream_route::<N>().with(warp::trace(
+            |info| tracing::debug_span!("Debugging headers", headers = ?info.request_headers()),
+        )))
         .or(health_route().with(warp::trace(
             |info| tracing::debug_span!("Debugging headers", headers = ?info.request_headers()),
+        )))
+        .or(live_route().with(warp::trace(
+            |info| tracing::debug_span!("Debugging headers", headers = ?info.request_headers()),
+        )))
+        .or(ready_route::<N>().with(warp::trace(
+            |info| tracing::debug_span!("Debugging headers", headers = ?info.request_headers()),
+        )))
+        .or(keys_route().with(warp::trace(
+            |info| tracing::debug_span!("Debugging headers", headers = ?info.request_headers()),
+        )))
+        .or(metrics_route(metrics()).with(warp::trace(
+            |info| tracing::debug_span!("Debugging headers", headers = ?info.request_headers()),
         )));
 
-    warp::serve(routes).run(([0, 0, 0, 0], port)).await;
+    // Stop on SIGINT or SIGTERM, once in-flight jobs finish or the timeout expires.
+    let address = SocketAddr::new(config.bind, config.port);
+    let shutdown_timeout = Duration::from_secs(config.shutdown_timeout_secs);
+    let (_, server) = warp::serve(routes.recover(handle_shutdown_rejection))
+        .bind_with_graceful_shutdown(address, drain(shutdown_timeout));
+    // Wait for the gRPC server to drain as well, within the same timeout.
+    #[cfg(feature = "grpc")]
+    let server = async move {
+        server.await;
+        if let Some(grpc) = grpc {
+            if let Ok(Err(error)) = grpc.await {
+                log::error!("The gRPC server failed: {error}");
+            }
+        }
+    };
+    serve_until_shutdown(server, shutdown_timeout).await;
 }
 
 #[tokio::main]
 async fn main() {
     let opt = Opt::from_args();
+    let print_config = opt.print_config;
 
-    match opt.network.as_str() {
-        "mainnet" => {
-            // Load the proving keys before running the service.
-            let _ = load_process::<MainnetV0>().expect("Failed to load a mainnet process");
-            run::<MainnetV0>(opt.port).await
-        }
-        "testnet" => {
-            // Load the proving keys before running the service.
-            let _ = load_process::<TestnetV0>().expect("Failed to load a testnet process");
-            run::<TestnetV0>(opt.port).await
-        }
-        "canary" => {
-            // Load the proving keys before running the service.
-            let _ = load_process::<TestnetV0>().expect("Failed to load a canary process");
-            run::<CanaryV0>(opt.port).await
-        }
+    // Resolve the configuration file, the environment and the flags, in that order.
+    let mut config = Config::<ExecuteConfig>::load(opt.config.as_deref())
+        .expect("Failed to load the configuration");
+    opt.apply(&mut config);
+    // Print the configuration before validating it, to help find the invalid value.
+    if print_config {
+        print!(
+            "{}",
+            config
+                .to_toml()
+                .expect("Failed to render the configuration")
+        );
+        return;
+    }
+    if let Err(error) = config.validate() {
+        panic!("Invalid configuration: {error}");
+    }
+
+    config.log_format.init();
+    if let Some(threads) = config.service.threads {
+        rayon::ThreadPoolBuilder::new()
+            .num_threads(threads)
+            .build_global()
+            .expect("Failed to build the worker thread pool");
+    }
+    set_settings(config.service.settings().expect("Invalid execute settings"))
+        .expect("Failed to initialize the settings");
+
+    match config.network.as_deref() {
+        Some("mainnet") => run::<MainnetV0>(config.service).await,
+        Some("testnet") => run::<TestnetV0>(config.service).await,
+        Some("canary") => run::<CanaryV0>(config.service).await,
         _ => panic!("Invalid network"),
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_flags_override_env_which_overrides_file() {
+        let path = std::env::temp_dir().join(format!("execute-config-{}.toml", std::process::id()));
+        let contents = r#"
+network = "testnet"
+
+[execute]
+port = 9001
+broadcast_attempts = 2
+batch_max_items = 7
+"#;
+        std::fs::write(&path, contents).unwrap();
+        std::env::set_var("ALEO_EXECUTE_PORT", "9002");
+        std::env::set_var("ALEO_EXECUTE_BROADCAST_ATTEMPTS", "3");
+
+        let opt = Opt::from_iter([
+            "execute-service",
+            "--config",
+            path.to_str().unwrap(),
+            "--port",
+            "9003",
+        ]);
+        let mut config = Config::<ExecuteConfig>::load(opt.config.as_deref()).unwrap();
+        opt.apply(&mut config);
+        std::env::remove_var("ALEO_EXECUTE_PORT");
+        std::env::remove_var("ALEO_EXECUTE_BROADCAST_ATTEMPTS");
+        std::fs::remove_file(&path).unwrap();
+
+        assert_eq!(config.network.as_deref(), Some("testnet"));
+        assert_eq!(config.service.port, 9003);
+        assert_eq!(config.service.broadcast_attempts, 3);
+        assert_eq!(config.service.batch_max_items, 7);
+    }
+}
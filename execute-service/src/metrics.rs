@@ -0,0 +1,82 @@
+// Copyright (C) 2019-2024 Aleo Systems Inc.
+// This file is part of the Aleo SDK library.
+
+// The Aleo SDK library is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// The Aleo SDK library is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with the Aleo SDK library. If not, see <https://www.gnu.org/licenses/>.
+
+use super::*;
+
+use prometheus::{HistogramOpts, HistogramVec};
+use std::ops::Deref;
+use std::sync::OnceLock;
+use std::time::Duration;
+
+// The histogram buckets, in seconds, spanning quick checks up to the slowest proofs.
+const DURATION_BUCKETS: &[f64] = &[
+    0.01, 0.05, 0.1, 0.25, 0.5, 1.0, 2.5, 5.0, 10.0, 30.0, 60.0, 120.0, 300.0,
+];
+
+/// The Prometheus metrics exported by the execute service.
+pub struct Metrics {
+    service: ServiceMetrics,
+    /// The time taken to load a proving key, by program and function.
+    pub key_load_duration: HistogramVec,
+}
+
+impl Metrics {
+    fn new() -> Result<Self> {
+        let service = ServiceMetrics::new("execute_service", DURATION_BUCKETS)?;
+        let key_load_duration = HistogramVec::new(
+            HistogramOpts::new(
+                "key_load_duration_seconds",
+                "The time taken to load a proving key",
+            )
+            .buckets(DURATION_BUCKETS.to_vec()),
+            &["program", "function"],
+        )?;
+        service.register(&key_load_duration)?;
+        Ok(Self {
+            service,
+            key_load_duration,
+        })
+    }
+
+    /// Records the duration of an execution phase of the function at `locator`.
+    pub fn observe_execution_phase<N: Network>(
+        &self,
+        phase: &str,
+        locator: &Locator<N>,
+        duration: Duration,
+    ) {
+        self.service.observe_phase(
+            phase,
+            &locator.program_id().to_string(),
+            &locator.resource().to_string(),
+            duration,
+        );
+    }
+}
+
+impl Deref for Metrics {
+    type Target = ServiceMetrics;
+
+    fn deref(&self) -> &ServiceMetrics {
+        &self.service
+    }
+}
+
+/// Returns the metrics of the service.
+pub fn metrics() -> &'static Metrics {
+    static METRICS: OnceLock<Metrics> = OnceLock::new();
+    METRICS.get_or_init(|| Metrics::new().expect("Failed to register the metrics"))
+}
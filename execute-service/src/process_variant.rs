@@ -16,6 +16,8 @@
 
 use super::*;
 
+use std::collections::BTreeSet;
+
 pub enum ProcessVariant {
     MainnetV0(Process<MainnetV0>),
     TestnetV0(Process<TestnetV0>),
@@ -23,69 +25,280 @@ pub enum ProcessVariant {
 }
 
 impl ProcessVariant {
-    pub fn execute(&self, bytes: &[u8]) -> Result<Vec<u8>> {
+    pub fn execute(
+        &self,
+        bytes: &[u8],
+        proving: ProvingMode,
+        on_phase: &(dyn Fn(Phase) + Sync),
+    ) -> Result<ExecuteOutput> {
         match self {
             ProcessVariant::MainnetV0(process) => {
-                Self::handle_execute::<AleoV0, MainnetV0>(process, bytes)
+                Self::handle_execute::<AleoV0, MainnetV0>(process, bytes, proving, on_phase)
             }
             ProcessVariant::TestnetV0(process) => {
-                Self::handle_execute::<AleoTestnetV0, TestnetV0>(process, bytes)
+                Self::handle_execute::<AleoTestnetV0, TestnetV0>(process, bytes, proving, on_phase)
             }
             ProcessVariant::CanaryV0(process) => {
-                Self::handle_execute::<AleoCanaryV0, CanaryV0>(process, bytes)
+                Self::handle_execute::<AleoCanaryV0, CanaryV0>(process, bytes, proving, on_phase)
+            }
+        }
+    }
+
+    pub fn execute_artifacts(
+        &self,
+        bytes: &[u8],
+        proving: ProvingMode,
+        on_phase: &(dyn Fn(Phase) + Sync),
+    ) -> Result<ArtifactsOutput> {
+        match self {
+            ProcessVariant::MainnetV0(process) => {
+                Self::handle_execute_artifacts::<AleoV0, MainnetV0>(
+                    process, bytes, proving, on_phase,
+                )
             }
+            ProcessVariant::TestnetV0(process) => Self::handle_execute_artifacts::<
+                AleoTestnetV0,
+                TestnetV0,
+            >(process, bytes, proving, on_phase),
+            ProcessVariant::CanaryV0(process) => Self::handle_execute_artifacts::<
+                AleoCanaryV0,
+                CanaryV0,
+            >(process, bytes, proving, on_phase),
+        }
+    }
+
+    pub fn resident_proving_keys(&self, targets: &[PreloadTarget]) -> Result<BTreeSet<String>> {
+        match self {
+            ProcessVariant::MainnetV0(process) => resident_proving_keys(process, targets),
+            ProcessVariant::TestnetV0(process) => resident_proving_keys(process, targets),
+            ProcessVariant::CanaryV0(process) => resident_proving_keys(process, targets),
         }
     }
 
     fn handle_execute<A: Aleo<Network = N>, N: Network>(
         process: &Process<N>,
         bytes: &[u8],
-    ) -> Result<Vec<u8>> {
+        proving: ProvingMode,
+        on_phase: &(dyn Fn(Phase) + Sync),
+    ) -> Result<ExecuteOutput> {
         // Deserialize the `ExecuteRequest`.
         let execute_request = ExecuteRequest::<N>::from_bytes_le(bytes)?;
-        // Initialize an RNG.
-        let rng = &mut rand_chacha::ChaCha20Rng::from_entropy();
 
+        // Compute the execution and the fee.
+        let (execution, fee, timings) =
+            Self::prove::<A, N>(process, execute_request.into(), proving, on_phase)?;
+        let fee = fee.ok_or_else(|| anyhow!("The fee was not proved"))?;
+
+        // Serialize the execution and the fee, for callers that want them on their own.
+        let execution_bytes = execution.to_bytes_le()?;
+        let fee_bytes = fee.to_bytes_le()?;
+
+        // Construct the transaction.
+        let transaction = Transaction::<N>::from_execution(execution, Some(fee))?;
+
+        // Serialize the transaction.
+        Ok(ExecuteOutput {
+            transaction: transaction.to_bytes_le()?,
+            execution: execution_bytes,
+            fee: fee_bytes,
+            timings,
+        })
+    }
+
+    fn handle_execute_artifacts<A: Aleo<Network = N>, N: Network>(
+        process: &Process<N>,
+        bytes: &[u8],
+        proving: ProvingMode,
+        on_phase: &(dyn Fn(Phase) + Sync),
+    ) -> Result<ArtifactsOutput> {
+        // Deserialize the `ExecutionRequest`.
+        let execution_request = ExecutionRequest::<N>::from_bytes_le(bytes)?;
+
+        // Compute the execution and the fee, if one was authorized.
+        let (execution, fee, timings) =
+            Self::prove::<A, N>(process, execution_request, proving, on_phase)?;
+
+        // Serialize the artifacts.
+        Ok(ArtifactsOutput {
+            execution: execution.to_bytes_le()?,
+            fee: fee.map(|fee| fee.to_bytes_le()).transpose()?,
+            timings,
+        })
+    }
+
+    /// Proves the function and, if one is authorized, the fee, verifying them if self-verification is enabled.
+    fn prove<A: Aleo<Network = N>, N: Network>(
+        process: &Process<N>,
+        request: ExecutionRequest<N>,
+        proving: ProvingMode,
+        on_phase: &(dyn Fn(Phase) + Sync),
+    ) -> Result<(Execution<N>, Option<Fee<N>>, PhaseTimings)> {
         // Get the function authorization.
-        let function_authorization = execute_request.function_authorization;
-        // Get the fee authorization.
-        let fee_authorization = execute_request.fee_authorization;
-        // Get the state root.
-        let state_root = execute_request.state_root;
-        // Get the state path.
-        let state_path = execute_request.state_path;
+        let function_authorization = request.function_authorization;
+        // Get the fee authorization, if any.
+        let fee_authorization = request.fee_authorization;
 
         // Construct the query.
-        let query = StaticQuery::<N>::new(state_root, state_path);
+        let query = StaticQuery::<N>::new(request.state_root, request.state_path);
 
         // Construct the locator of the main function.
         let locator = {
             let request = function_authorization.peek_next()?;
-            Locator::new(*request.program_id(), *request.function_name()).to_string()
+            Locator::new(*request.program_id(), *request.function_name())
         };
+        // Construct the locator of the fee function.
+        let fee_locator = match &fee_authorization {
+            Some(fee_authorization) => {
+                let request = fee_authorization.peek_next()?;
+                Some(Locator::new(
+                    *request.program_id(),
+                    *request.function_name(),
+                ))
+            }
+            None => None,
+        };
+
+        // Record the duration of each phase on a span, for diagnosing slow requests.
+        let span = tracing::info_span!(
+            "execute",
+            program = %locator.program_id(),
+            function = %locator.resource(),
+            execute_ms = tracing::field::Empty,
+            prepare_ms = tracing::field::Empty,
+            prove_execution_ms = tracing::field::Empty,
+            fee_execute_ms = tracing::field::Empty,
+            fee_prepare_ms = tracing::field::Empty,
+            prove_fee_ms = tracing::field::Empty,
+        );
+        let _guard = span.enter();
+
+        // Prove the fee, if one is authorized.
+        let prove_fee = |query: StaticQuery<N>| match (fee_authorization, &fee_locator) {
+            (Some(fee_authorization), Some(fee_locator)) => {
+                Self::prove_fee::<A, N>(process, fee_authorization, query, fee_locator, on_phase)
+                    .map(Some)
+            }
+            _ => Ok(None),
+        };
+
+        // Compute the execution and the fee, either at the same time or one after the other.
+        let ((execution, function_timings), fee) = match proving {
+            ProvingMode::Parallel => {
+                let fee_query = query.clone();
+                let (function, fee) = rayon::join(
+                    || {
+                        span.in_scope(|| {
+                            Self::prove_function::<A, N>(
+                                process,
+                                function_authorization,
+                                query,
+                                &locator,
+                                on_phase,
+                            )
+                        })
+                    },
+                    || span.in_scope(|| prove_fee(fee_query)),
+                );
+                (function?, fee?)
+            }
+            ProvingMode::Sequential => (
+                Self::prove_function::<A, N>(
+                    process,
+                    function_authorization,
+                    query.clone(),
+                    &locator,
+                    on_phase,
+                )?,
+                prove_fee(query)?,
+            ),
+        };
+        let (fee, timings) = match fee {
+            Some((fee, fee_timings)) => (
+                Some(fee),
+                PhaseTimings {
+                    fee_execute: fee_timings.fee_execute,
+                    fee_prepare: fee_timings.fee_prepare,
+                    prove_fee: fee_timings.prove_fee,
+                    ..function_timings
+                },
+            ),
+            None => (None, function_timings),
+        };
+
+        // Verify the execution and the fee, if self-verification is enabled.
+        if settings().verify {
+            match &fee {
+                Some(fee) => verify_transaction(process, &execution, fee)?,
+                None => verify_execution(process, &execution)?,
+            }
+        }
+
+        Ok((execution, fee, timings))
+    }
+
+    /// Executes, prepares and proves the function authorization.
+    fn prove_function<A: Aleo<Network = N>, N: Network>(
+        process: &Process<N>,
+        authorization: Authorization<N>,
+        query: StaticQuery<N>,
+        locator: &Locator<N>,
+        on_phase: &(dyn Fn(Phase) + Sync),
+    ) -> Result<(Execution<N>, PhaseTimings)> {
+        // Initialize an RNG.
+        let rng = &mut rand_chacha::ChaCha20Rng::from_entropy();
+        let mut timings = PhaseTimings::default();
+
+        // Load the proving key first, since executing would load it without timing the load.
+        load_proving_key(process, locator)?;
 
         // Execute the function authorization.
-        let (_, mut trace) = process.execute::<A, _>(function_authorization, rng)?;
+        let (_, mut trace) = timings.time(Phase::Execute, locator, || {
+            process.execute::<A, _>(authorization, rng)
+        })?;
+        on_phase(Phase::Execute);
 
         // Prepare the trace.
-        trace.prepare(query.clone())?;
+        timings.time(Phase::Prepare, locator, || trace.prepare(query))?;
+        on_phase(Phase::Prepare);
 
         // Compute the proof and construct the execution.
-        let execution = trace.prove_execution::<A, _>(&locator, rng)?;
+        let execution = timings.time(Phase::ProveExecution, locator, || {
+            trace.prove_execution::<A, _>(&locator.to_string(), rng)
+        })?;
+        on_phase(Phase::ProveExecution);
+
+        Ok((execution, timings))
+    }
+
+    /// Executes, prepares and proves the fee authorization.
+    fn prove_fee<A: Aleo<Network = N>, N: Network>(
+        process: &Process<N>,
+        authorization: Authorization<N>,
+        query: StaticQuery<N>,
+        locator: &Locator<N>,
+        on_phase: &(dyn Fn(Phase) + Sync),
+    ) -> Result<(Fee<N>, PhaseTimings)> {
+        // Initialize an RNG.
+        let rng = &mut rand_chacha::ChaCha20Rng::from_entropy();
+        let mut timings = PhaseTimings::default();
+
+        // Load the proving key first, since executing would load it without timing the load.
+        load_proving_key(process, locator)?;
 
         // Execute the fee authorization.
-        let (_, mut trace) = process.execute::<A, _>(fee_authorization, rng)?;
+        let (_, mut trace) = timings.time(Phase::FeeExecute, locator, || {
+            process.execute::<A, _>(authorization, rng)
+        })?;
+        on_phase(Phase::FeeExecute);
 
         // Prepare the trace.
-        trace.prepare(query)?;
+        timings.time(Phase::FeePrepare, locator, || trace.prepare(query))?;
+        on_phase(Phase::FeePrepare);
 
         // Compute the proof and construct the fee.
-        let fee = trace.prove_fee::<A, _>(rng)?;
+        let fee = timings.time(Phase::ProveFee, locator, || trace.prove_fee::<A, _>(rng))?;
+        on_phase(Phase::ProveFee);
 
-        // Construct the transaction.
-        let transaction = Transaction::<N>::from_execution(execution, Some(fee))?;
-
-        // Serialize the transaction.
-        transaction.to_bytes_le()
+        Ok((fee, timings))
     }
 }
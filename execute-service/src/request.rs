@@ -19,31 +19,226 @@ use super::*;
 use snarkvm::prelude::{error, IoResult};
 use std::io::{Read, Write};
 
+/// The magic bytes that open a versioned `ExecuteRequest`. The legacy format starts with
+/// the version byte of the function authorization instead, which never matches.
+pub const EXECUTE_REQUEST_MAGIC: [u8; 4] = *b"AEXR";
+
+/// The newest `ExecuteRequest` format version this build reads and writes.
+pub const EXECUTE_REQUEST_VERSION: u16 = 1;
+
+/// The magic bytes that open an `ExecutionRequest`.
+pub const EXECUTION_REQUEST_MAGIC: [u8; 4] = *b"AEXN";
+
+/// The newest `ExecutionRequest` format version this build reads and writes.
+pub const EXECUTION_REQUEST_VERSION: u16 = 1;
+
+/// The magic bytes that open a `BatchRequest`.
+pub const BATCH_REQUEST_MAGIC: [u8; 4] = *b"AEXB";
+
+/// The newest `BatchRequest` format version this build reads and writes.
+pub const BATCH_REQUEST_VERSION: u16 = 1;
+
+/// A tagged option in the tail of a versioned `ExecuteRequest`.
+/// Readers skip the tags they do not know, so that options can be added without a new version.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct RequestOption {
+    pub tag: u16,
+    pub value: Vec<u8>,
+}
+
 #[derive(Clone, Debug)]
 pub struct ExecuteRequest<N: Network> {
     pub function_authorization: Authorization<N>,
     pub fee_authorization: Authorization<N>,
     pub state_root: Option<N::StateRoot>,
     pub state_path: Option<StatePath<N>>,
+    pub options: Vec<RequestOption>,
+}
+
+impl<N: Network> ExecuteRequest<N> {
+    /// Writes the request in the unversioned format, for services that predate the envelope.
+    /// The options are dropped.
+    pub fn write_legacy_le<W: Write>(&self, mut writer: W) -> IoResult<()> {
+        self.function_authorization.write_le(&mut writer)?;
+        self.fee_authorization.write_le(&mut writer)?;
+        write_state(&mut writer, &self.state_root, &self.state_path)
+    }
+
+    // Reads the fields shared by every format version.
+    fn read_body<R: Read>(mut reader: R) -> IoResult<Self> {
+        let function_authorization = Authorization::read_le(&mut reader)?;
+        let fee_authorization = Authorization::read_le(&mut reader)?;
+        let (state_root, state_path) = read_state::<N, _>(&mut reader)?;
+        Ok(Self {
+            function_authorization,
+            fee_authorization,
+            state_root,
+            state_path,
+            options: Vec::new(),
+        })
+    }
 }
 
 impl<N: Network> FromBytes for ExecuteRequest<N> {
+    /// Reads either a versioned request or a legacy, unversioned one.
+    fn read_le<R: Read>(mut reader: R) -> IoResult<Self>
+    where
+        Self: Sized,
+    {
+        let mut magic = [0u8; 4];
+        reader.read_exact(&mut magic)?;
+        // Without the magic bytes, this is a legacy request. Put the bytes back and read it as such.
+        if magic != EXECUTE_REQUEST_MAGIC {
+            return Self::read_body((&magic[..]).chain(reader));
+        }
+
+        let version = u16::read_le(&mut reader)?;
+        match version {
+            1 => {
+                let mut request = Self::read_body(&mut reader)?;
+                request.options = read_options(&mut reader)?;
+                Ok(request)
+            }
+            _ => Err(error(format!(
+                "Unsupported execute request version {version}"
+            ))),
+        }
+    }
+}
+
+impl<N: Network> ToBytes for ExecuteRequest<N> {
+    /// Writes the request in the newest versioned format.
+    fn write_le<W: Write>(&self, mut writer: W) -> IoResult<()>
+    where
+        Self: Sized,
+    {
+        writer.write_all(&EXECUTE_REQUEST_MAGIC)?;
+        EXECUTE_REQUEST_VERSION.write_le(&mut writer)?;
+        self.write_legacy_le(&mut writer)?;
+        write_options(&mut writer, &self.options)
+    }
+}
+
+// Reads the magic bytes of a versioned format, then returns its version.
+fn read_version<R: Read>(mut reader: R, magic: [u8; 4], name: &str) -> IoResult<u16> {
+    let mut found = [0u8; 4];
+    reader.read_exact(&mut found)?;
+    if found != magic {
+        return Err(error(format!("Not a versioned {name}")));
+    }
+    u16::read_le(&mut reader)
+}
+
+// Reads the optional state root and state path, each behind a presence flag.
+fn read_state<N: Network, R: Read>(
+    mut reader: R,
+) -> IoResult<(Option<N::StateRoot>, Option<StatePath<N>>)> {
+    let state_root = match u8::read_le(&mut reader)? {
+        0 => None,
+        1 => Some(N::StateRoot::read_le(&mut reader)?),
+        _ => return Err(error("Invalid state root flag")),
+    };
+    let state_path = match u8::read_le(&mut reader)? {
+        0 => None,
+        1 => Some(StatePath::read_le(&mut reader)?),
+        _ => return Err(error("Invalid state path flag")),
+    };
+    Ok((state_root, state_path))
+}
+
+// Writes the optional state root and state path, each behind a presence flag.
+fn write_state<N: Network, W: Write>(
+    mut writer: W,
+    state_root: &Option<N::StateRoot>,
+    state_path: &Option<StatePath<N>>,
+) -> IoResult<()> {
+    match state_root {
+        None => 0u8.write_le(&mut writer)?,
+        Some(state_root) => {
+            1u8.write_le(&mut writer)?;
+            state_root.write_le(&mut writer)?
+        }
+    }
+    match state_path {
+        None => 0u8.write_le(&mut writer)?,
+        Some(state_path) => {
+            1u8.write_le(&mut writer)?;
+            state_path.write_le(&mut writer)?
+        }
+    }
+    Ok(())
+}
+
+/// Reads the tagged options: a u16 count, then a u16 tag and a u32 length before each value.
+pub fn read_options<R: Read>(mut reader: R) -> IoResult<Vec<RequestOption>> {
+    let count = u16::read_le(&mut reader)?;
+    let mut options = Vec::new();
+    for _ in 0..count {
+        let tag = u16::read_le(&mut reader)?;
+        let length = u32::read_le(&mut reader)?;
+        let mut value = Vec::new();
+        (&mut reader).take(length as u64).read_to_end(&mut value)?;
+        if value.len() != length as usize {
+            return Err(error("Truncated request option"));
+        }
+        options.push(RequestOption { tag, value });
+    }
+    Ok(options)
+}
+
+/// Writes the tagged options in the format read by `read_options`.
+pub fn write_options<W: Write>(mut writer: W, options: &[RequestOption]) -> IoResult<()> {
+    u16::try_from(options.len())
+        .map_err(|_| error("Too many request options"))?
+        .write_le(&mut writer)?;
+    for option in options {
+        option.tag.write_le(&mut writer)?;
+        u32::try_from(option.value.len())
+            .map_err(|_| error("Request option too large"))?
+            .write_le(&mut writer)?;
+        writer.write_all(&option.value)?;
+    }
+    Ok(())
+}
+
+/// A function authorization to prove on its own, with an optional fee authorization.
+#[derive(Clone, Debug)]
+pub struct ExecutionRequest<N: Network> {
+    pub function_authorization: Authorization<N>,
+    pub fee_authorization: Option<Authorization<N>>,
+    pub state_root: Option<N::StateRoot>,
+    pub state_path: Option<StatePath<N>>,
+}
+
+impl<N: Network> From<ExecuteRequest<N>> for ExecutionRequest<N> {
+    fn from(request: ExecuteRequest<N>) -> Self {
+        Self {
+            function_authorization: request.function_authorization,
+            fee_authorization: Some(request.fee_authorization),
+            state_root: request.state_root,
+            state_path: request.state_path,
+        }
+    }
+}
+
+impl<N: Network> FromBytes for ExecutionRequest<N> {
     fn read_le<R: Read>(mut reader: R) -> IoResult<Self>
     where
         Self: Sized,
     {
+        let version = read_version(&mut reader, EXECUTION_REQUEST_MAGIC, "execution request")?;
+        if version != 1 {
+            return Err(error(format!(
+                "Unsupported execution request version {version}"
+            )));
+        }
         let function_authorization = Authorization::read_le(&mut reader)?;
-        let fee_authorization = Authorization::read_le(&mut reader)?;
-        let state_root = match u8::read_le(&mut reader)? {
+        let fee_authorization = match u8::read_le(&mut reader)? {
             0 => None,
-            1 => Some(N::StateRoot::read_le(&mut reader)?),
-            _ => return Err(error("Invalid state root flag")),
-        };
-        let state_path = match u8::read_le(&mut reader)? {
-            0 => None,
-            1 => Some(StatePath::read_le(&mut reader)?),
-            _ => return Err(error("Invalid state path flag")),
+            1 => Some(Authorization::read_le(&mut reader)?),
+            _ => return Err(error("Invalid fee authorization flag")),
         };
+        let (state_root, state_path) = read_state::<N, _>(&mut reader)?;
         Ok(Self {
             function_authorization,
             fee_authorization,
@@ -53,27 +248,279 @@ impl<N: Network> FromBytes for ExecuteRequest<N> {
     }
 }
 
-impl<N: Network> ToBytes for ExecuteRequest<N> {
+impl<N: Network> ToBytes for ExecutionRequest<N> {
     fn write_le<W: Write>(&self, mut writer: W) -> IoResult<()>
     where
         Self: Sized,
     {
+        writer.write_all(&EXECUTION_REQUEST_MAGIC)?;
+        EXECUTION_REQUEST_VERSION.write_le(&mut writer)?;
         self.function_authorization.write_le(&mut writer)?;
-        self.fee_authorization.write_le(&mut writer)?;
-        match &self.state_root {
+        match &self.fee_authorization {
             None => 0u8.write_le(&mut writer)?,
-            Some(state_root) => {
+            Some(fee_authorization) => {
                 1u8.write_le(&mut writer)?;
-                state_root.write_le(&mut writer)?
+                fee_authorization.write_le(&mut writer)?
             }
         }
-        match &self.state_path {
-            None => 0u8.write_le(&mut writer)?,
-            Some(state_path) => {
-                1u8.write_le(&mut writer)?;
-                state_path.write_le(&mut writer)?
+        write_state(&mut writer, &self.state_root, &self.state_path)
+    }
+}
+
+/// What `POST /execute` returns.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum OutputMode {
+    /// The serialized transaction.
+    #[default]
+    Transaction,
+    /// The serialized execution and fee, as separate artifacts.
+    Artifacts,
+}
+
+/// The query parameters of `POST /execute`.
+#[derive(Clone, Debug, Default, Serialize, Deserialize)]
+pub struct ExecuteParams {
+    #[serde(default)]
+    pub output: OutputMode,
+}
+
+/// A batch of serialized `ExecuteRequest`s, each prefixed with its length, behind a versioned header.
+/// The items are kept serialized so that a malformed item only fails that item.
+#[derive(Clone, Debug, Default)]
+pub struct BatchRequest {
+    pub requests: Vec<Vec<u8>>,
+}
+
+impl FromBytes for BatchRequest {
+    fn read_le<R: Read>(mut reader: R) -> IoResult<Self>
+    where
+        Self: Sized,
+    {
+        let version = read_version(&mut reader, BATCH_REQUEST_MAGIC, "batch request")?;
+        if version != 1 {
+            return Err(error(format!(
+                "Unsupported batch request version {version}"
+            )));
+        }
+        let count = u32::read_le(&mut reader)?;
+        let mut requests = Vec::new();
+        for _ in 0..count {
+            let length = u32::read_le(&mut reader)?;
+            let mut request = Vec::new();
+            (&mut reader)
+                .take(length as u64)
+                .read_to_end(&mut request)?;
+            if request.len() != length as usize {
+                return Err(error("Truncated batch item"));
             }
+            requests.push(request);
+        }
+        Ok(Self { requests })
+    }
+}
+
+impl ToBytes for BatchRequest {
+    fn write_le<W: Write>(&self, mut writer: W) -> IoResult<()>
+    where
+        Self: Sized,
+    {
+        writer.write_all(&BATCH_REQUEST_MAGIC)?;
+        BATCH_REQUEST_VERSION.write_le(&mut writer)?;
+        u32::try_from(self.requests.len())
+            .map_err(|_| error("Too many batch items"))?
+            .write_le(&mut writer)?;
+        for request in &self.requests {
+            u32::try_from(request.len())
+                .map_err(|_| error("Batch item too large"))?
+                .write_le(&mut writer)?;
+            writer.write_all(request)?;
         }
         Ok(())
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use snarkvm::prelude::{Address, Literal, PrivateKey, Value, U64};
+    use std::path::PathBuf;
+
+    type CurrentNetwork = MainnetV0;
+
+    // Builds the same `ExecuteRequest` on every run, from a fixed key and a seeded RNG.
+    fn sample_execute_request() -> ExecuteRequest<CurrentNetwork> {
+        let process = Process::<CurrentNetwork>::load().unwrap();
+        let rng = &mut rand_chacha::ChaCha20Rng::seed_from_u64(1234);
+        let private_key = PrivateKey::<CurrentNetwork>::new(rng).unwrap();
+        let inputs = [
+            Value::<CurrentNetwork>::from(Literal::Address(
+                Address::try_from(&private_key).unwrap(),
+            )),
+            Value::from(Literal::U64(U64::new(100))),
+        ];
+        let function_authorization = process
+            .authorize::<AleoV0, _>(
+                &private_key,
+                "credits.aleo",
+                "transfer_public",
+                inputs.iter(),
+                rng,
+            )
+            .unwrap();
+        let execution_id = function_authorization.to_execution_id().unwrap();
+        let fee_authorization = process
+            .authorize_fee_public::<AleoV0, _>(&private_key, 100000, 1000, execution_id, rng)
+            .unwrap();
+        ExecuteRequest {
+            function_authorization,
+            fee_authorization,
+            state_root: None,
+            state_path: None,
+            options: vec![RequestOption {
+                tag: 0x7fff,
+                value: b"reserved".to_vec(),
+            }],
+        }
+    }
+
+    // Compares `bytes` with the committed golden vector `name`. With `UPDATE_GOLDEN` set,
+    // the vector is rewritten instead; commit the result along with the format change.
+    fn check_golden(name: &str, bytes: &[u8]) {
+        let path = PathBuf::from(env!("CARGO_MANIFEST_DIR"))
+            .join("tests")
+            .join("vectors")
+            .join(name);
+        if std::env::var_os("UPDATE_GOLDEN").is_some() {
+            std::fs::create_dir_all(path.parent().unwrap()).unwrap();
+            std::fs::write(&path, bytes).unwrap();
+        }
+        let expected = std::fs::read(&path).unwrap_or_else(|error| {
+            panic!("Missing golden vector {name} ({error}), run with UPDATE_GOLDEN=1 to create it")
+        });
+        assert_eq!(expected, bytes, "{name} no longer matches");
+    }
+
+    #[test]
+    fn execute_request_golden_vectors() {
+        let request = sample_execute_request();
+
+        let mut legacy = Vec::new();
+        request.write_legacy_le(&mut legacy).unwrap();
+        check_golden("execute_request_legacy.bin", &legacy);
+        check_golden("execute_request_v1.bin", &request.to_bytes_le().unwrap());
+    }
+
+    #[test]
+    fn execution_request_golden_vectors() {
+        let request = ExecutionRequest::from(sample_execute_request());
+        let bytes = request.to_bytes_le().unwrap();
+        assert_eq!(&bytes[..6], b"AEXN\x01\x00");
+        check_golden("execution_request_v1.bin", &bytes);
+        let decoded = ExecutionRequest::<CurrentNetwork>::from_bytes_le(&bytes).unwrap();
+        assert_eq!(decoded.to_bytes_le().unwrap(), bytes);
+    }
+
+    #[test]
+    fn execution_request_rejects_unknown_versions() {
+        let mut bytes = ExecutionRequest::from(sample_execute_request())
+            .to_bytes_le()
+            .unwrap();
+        bytes[4] = 2;
+        assert!(ExecutionRequest::<CurrentNetwork>::from_bytes_le(&bytes).is_err());
+    }
+
+    #[test]
+    fn batch_request_golden_vector() {
+        let batch = BatchRequest {
+            requests: vec![vec![1, 2, 3], vec![]],
+        };
+        check_golden("batch_request_v1.bin", &batch.to_bytes_le().unwrap());
+    }
+
+    #[test]
+    fn batch_request_rejects_unversioned_bytes() {
+        let mut bytes = BatchRequest {
+            requests: vec![vec![1, 2, 3]],
+        }
+        .to_bytes_le()
+        .unwrap();
+        assert!(BatchRequest::from_bytes_le(&bytes[6..]).is_err());
+        bytes[4] = 2;
+        assert!(BatchRequest::from_bytes_le(&bytes).is_err());
+    }
+
+    #[test]
+    fn execute_request_reads_every_version() {
+        let request = sample_execute_request();
+
+        // A legacy request has no options, and round trips through the legacy writer.
+        let mut legacy = Vec::new();
+        request.write_legacy_le(&mut legacy).unwrap();
+        let decoded = ExecuteRequest::<CurrentNetwork>::from_bytes_le(&legacy).unwrap();
+        assert!(decoded.options.is_empty());
+        let mut reencoded = Vec::new();
+        decoded.write_legacy_le(&mut reencoded).unwrap();
+        assert_eq!(reencoded, legacy);
+
+        // A versioned request keeps its options.
+        let versioned = request.to_bytes_le().unwrap();
+        assert_eq!(&versioned[..6], b"AEXR\x01\x00");
+        let decoded = ExecuteRequest::<CurrentNetwork>::from_bytes_le(&versioned).unwrap();
+        assert_eq!(decoded.options, request.options);
+        assert_eq!(decoded.to_bytes_le().unwrap(), versioned);
+    }
+
+    #[test]
+    fn execute_request_rejects_unknown_versions() {
+        let mut bytes = sample_execute_request().to_bytes_le().unwrap();
+        bytes[4] = 2;
+        assert!(ExecuteRequest::<CurrentNetwork>::from_bytes_le(&bytes).is_err());
+    }
+
+    #[test]
+    fn request_options_are_pinned() {
+        let options = vec![
+            RequestOption {
+                tag: 1,
+                value: vec![0xaa, 0xbb],
+            },
+            RequestOption {
+                tag: 0x0203,
+                value: vec![],
+            },
+        ];
+        let mut bytes = Vec::new();
+        write_options(&mut bytes, &options).unwrap();
+        assert_eq!(
+            bytes,
+            [
+                0x02, 0x00, // count
+                0x01, 0x00, 0x02, 0x00, 0x00, 0x00, 0xaa, 0xbb, // tag 1
+                0x03, 0x02, 0x00, 0x00, 0x00, 0x00, // tag 0x0203
+            ]
+        );
+        assert_eq!(read_options(&bytes[..]).unwrap(), options);
+        assert!(read_options(&bytes[..bytes.len() - 3]).is_err());
+    }
+
+    #[test]
+    fn batch_request_round_trips() {
+        let batch = BatchRequest {
+            requests: vec![vec![1, 2, 3], vec![], vec![4; 300]],
+        };
+        let bytes = batch.to_bytes_le().unwrap();
+        let decoded = BatchRequest::from_bytes_le(&bytes).unwrap();
+        assert_eq!(decoded.requests, batch.requests);
+    }
+
+    #[test]
+    fn batch_request_rejects_truncated_items() {
+        let batch = BatchRequest {
+            requests: vec![vec![1, 2, 3, 4]],
+        };
+        let bytes = batch.to_bytes_le().unwrap();
+        assert!(BatchRequest::from_bytes_le(&bytes[..bytes.len() - 1]).is_err());
+    }
+}
@@ -0,0 +1,157 @@
+// Copyright (C) 2019-2024 Aleo Systems Inc.
+// This file is part of the Aleo SDK library.
+
+// The Aleo SDK library is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// The Aleo SDK library is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with the Aleo SDK library. If not, see <https://www.gnu.org/licenses/>.
+
+use super::*;
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct WorkerKeys {
+    pub worker: usize,
+    pub proving_keys: Vec<String>,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct KeysResponse {
+    pub workers: Vec<WorkerKeys>,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct WorkerReadiness {
+    pub worker: usize,
+    pub process_loaded: bool,
+    pub proving_keys: Vec<String>,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct ReadinessResponse {
+    pub ready: bool,
+    pub draining: bool,
+    pub network: String,
+    pub version: String,
+    pub queue_depth: i64,
+    pub preload: Vec<String>,
+    pub workers: Vec<WorkerReadiness>,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct BroadcastResponse {
+    pub transaction_id: String,
+    pub transaction: Vec<u8>,
+    pub node_status: u16,
+    pub node_response: String,
+    pub attempts: u32,
+}
+
+/// A serialized execution and, if one was proved, a serialized fee.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct ArtifactsResponse {
+    pub execution: Vec<u8>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub fee: Option<Vec<u8>>,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct ErrorResponse {
+    pub code: String,
+    pub message: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub stage: Option<VerificationStage>,
+}
+
+impl ErrorResponse {
+    pub fn new(code: &str, message: impl std::fmt::Display) -> Self {
+        Self {
+            code: code.to_string(),
+            message: message.to_string(),
+            stage: None,
+        }
+    }
+}
+
+impl From<&VerificationError> for ErrorResponse {
+    fn from(error: &VerificationError) -> Self {
+        Self {
+            code: "verification_failed".to_string(),
+            message: error.message.clone(),
+            stage: Some(error.stage),
+        }
+    }
+}
+
+/// The outcome of one item of a batch: either the serialized transaction or the error.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct BatchItem {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub transaction: Option<Vec<u8>>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub error: Option<ErrorResponse>,
+}
+
+/// The items of a batch, in the order of the request.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct BatchResponse {
+    pub items: Vec<BatchItem>,
+}
+
+/// An event of the `POST /execute/stream` route, sent as each stage of an execution finishes.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[serde(tag = "stage", rename_all = "snake_case")]
+pub enum ProgressEvent {
+    Queued,
+    AuthorizationExecuted,
+    TracePrepared,
+    ExecutionProved,
+    FeeAuthorizationExecuted,
+    FeeTracePrepared,
+    FeeProved,
+    Done {
+        transaction: Vec<u8>,
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        broadcast: Option<BroadcastResponse>,
+    },
+    Failed {
+        error: ErrorResponse,
+    },
+}
+
+impl ProgressEvent {
+    /// The name of the event, as sent in the `event` field.
+    pub fn name(&self) -> &'static str {
+        match self {
+            ProgressEvent::Queued => "queued",
+            ProgressEvent::AuthorizationExecuted => "authorization_executed",
+            ProgressEvent::TracePrepared => "trace_prepared",
+            ProgressEvent::ExecutionProved => "execution_proved",
+            ProgressEvent::FeeAuthorizationExecuted => "fee_authorization_executed",
+            ProgressEvent::FeeTracePrepared => "fee_trace_prepared",
+            ProgressEvent::FeeProved => "fee_proved",
+            ProgressEvent::Done { .. } => "done",
+            ProgressEvent::Failed { .. } => "failed",
+        }
+    }
+}
+
+impl From<Phase> for ProgressEvent {
+    fn from(phase: Phase) -> Self {
+        match phase {
+            Phase::Execute => ProgressEvent::AuthorizationExecuted,
+            Phase::Prepare => ProgressEvent::TracePrepared,
+            Phase::ProveExecution => ProgressEvent::ExecutionProved,
+            Phase::FeeExecute => ProgressEvent::FeeAuthorizationExecuted,
+            Phase::FeePrepare => ProgressEvent::FeeTracePrepared,
+            Phase::ProveFee => ProgressEvent::FeeProved,
+        }
+    }
+}
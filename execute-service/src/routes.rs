@@ -16,32 +16,383 @@
 
 use super::*;
 
-use warp::{http::Response, hyper::body::Bytes, Filter, Rejection, Reply};
+use tokio::sync::mpsc;
+use tokio_stream::{wrappers::UnboundedReceiverStream, StreamExt};
+use warp::{
+    http::{Response, StatusCode},
+    hyper::body::Bytes,
+    sse::Event,
+    Filter, Rejection, Reply,
+};
 
 // POST /execute
 pub fn execute_route<N: Network>() -> impl Filter<Extract = impl Reply, Error = Rejection> + Clone {
+    let client = broadcast_client();
     warp::post()
+        .and(accepting_work())
         .and(warp::path("execute"))
         .and(warp::path::end())
-        .and(warp::body::content_length_limit(32 * 1024)) // 32 KiB
+        .and(warp::body::content_length_limit(settings().body_limit))
+        .and(warp::body::bytes())
+        .and(warp::query::<ExecuteParams>())
+        .and(warp::any().map(move || client.clone()))
+        .and_then(
+            |request_bytes: Bytes, params: ExecuteParams, client: reqwest::Client| async move {
+                let output = match execute_once::<N>(request_bytes).await {
+                    Ok(output) => output,
+                    Err(error) => match error.downcast_ref::<VerificationError>() {
+                        Some(error) => {
+                            metrics().record_request("execute", "verification_failed");
+                            return Ok(error_reply(
+                                StatusCode::UNPROCESSABLE_ENTITY,
+                                &ErrorResponse::from(error),
+                            ));
+                        }
+                        None => {
+                            metrics().record_request("execute", "error");
+                            return Ok(error_reply(
+                                StatusCode::UNPROCESSABLE_ENTITY,
+                                &ErrorResponse::new("execution_failed", &error),
+                            ));
+                        }
+                    },
+                };
+                metrics().record_request("execute", "success");
+                // Return the execution and the fee separately, if requested.
+                if params.output == OutputMode::Artifacts {
+                    let response = ArtifactsResponse {
+                        execution: output.execution,
+                        fee: Some(output.fee),
+                    };
+                    return Ok(warp::reply::with_header(
+                        warp::reply::json(&response),
+                        "server-timing",
+                        output.timings.to_server_timing(),
+                    )
+                    .into_response());
+                }
+                // Forward the transaction to the node, if broadcasting is enabled.
+                if let Some(broadcast_settings) = &settings().broadcast {
+                    let server_timing = output.timings.to_server_timing();
+                    let response =
+                        broadcast_reply::<N>(&client, broadcast_settings, output.transaction).await;
+                    return Ok(
+                        warp::reply::with_header(response, "server-timing", server_timing)
+                            .into_response(),
+                    );
+                }
+                let response = match Response::builder()
+                    .header("content-type", "application/octet-stream")
+                    .header("server-timing", output.timings.to_server_timing())
+                    .body(output.transaction)
+                {
+                    Ok(response) => response,
+                    Err(error) => {
+                        return Ok(error_reply(
+                            StatusCode::INTERNAL_SERVER_ERROR,
+                            &ErrorResponse::new("execution_failed", &error),
+                        ))
+                    }
+                };
+                Ok::<_, Rejection>(response.into_response())
+            },
+        )
+}
+
+// POST /execution
+pub fn execution_route<N: Network>() -> impl Filter<Extract = impl Reply, Error = Rejection> + Clone
+{
+    warp::post()
+        .and(accepting_work())
+        .and(warp::path("execution"))
+        .and(warp::path::end())
+        .and(warp::body::content_length_limit(settings().body_limit))
         .and(warp::body::bytes())
         .and_then(|request_bytes: Bytes| async move {
-            let response_bytes = match tokio_rayon::spawn_fifo(|| execute::<N>(request_bytes)).await
-            {
-                Ok(response_bytes) => response_bytes,
-                Err(_) => return Err(warp::reject()),
+            let output = match execute_artifacts_once::<N>(request_bytes).await {
+                Ok(output) => output,
+                Err(error) => match error.downcast_ref::<VerificationError>() {
+                    Some(error) => {
+                        metrics().record_request("execution", "verification_failed");
+                        return Ok(error_reply(
+                            StatusCode::UNPROCESSABLE_ENTITY,
+                            &ErrorResponse::from(error),
+                        ));
+                    }
+                    None => {
+                        metrics().record_request("execution", "error");
+                        return Ok(error_reply(
+                            StatusCode::UNPROCESSABLE_ENTITY,
+                            &ErrorResponse::new("execution_failed", &error),
+                        ));
+                    }
+                },
             };
-            let response = match Response::builder()
-                .header("content-type", "application/octet-stream")
-                .body(response_bytes)
-            {
-                Ok(response) => response,
-                Err(_) => return Err(warp::reject()),
+            metrics().record_request("execution", "success");
+            let response = ArtifactsResponse {
+                execution: output.execution,
+                fee: output.fee,
             };
-            Ok(response)
+            Ok::<_, Rejection>(
+                warp::reply::with_header(
+                    warp::reply::json(&response),
+                    "server-timing",
+                    output.timings.to_server_timing(),
+                )
+                .into_response(),
+            )
         })
 }
 
+// POST /execute/batch
+pub fn execute_batch_route<N: Network>(
+) -> impl Filter<Extract = impl Reply, Error = Rejection> + Clone {
+    warp::post()
+        .and(accepting_work())
+        .and(warp::path!("execute" / "batch"))
+        .and(warp::body::content_length_limit(
+            settings().batch_body_limit,
+        ))
+        .and(warp::body::bytes())
+        .and_then(|request_bytes: Bytes| async move {
+            let batch = match BatchRequest::from_bytes_le(&request_bytes) {
+                Ok(batch) => batch,
+                Err(error) => {
+                    metrics().record_request("execute_batch", "error");
+                    return Ok(error_reply(
+                        StatusCode::BAD_REQUEST,
+                        &ErrorResponse::new("invalid_batch", error),
+                    ));
+                }
+            };
+            if batch.requests.len() > settings().batch_max_items {
+                metrics().record_request("execute_batch", "error");
+                return Ok(error_reply(
+                    StatusCode::PAYLOAD_TOO_LARGE,
+                    &ErrorResponse::new(
+                        "batch_too_large",
+                        format!("At most {} items are allowed", settings().batch_max_items),
+                    ),
+                ));
+            }
+            // Hold each item to the limit of a single request.
+            if let Some(index) = batch
+                .requests
+                .iter()
+                .position(|request| request.len() as u64 > settings().body_limit)
+            {
+                metrics().record_request("execute_batch", "error");
+                return Ok(error_reply(
+                    StatusCode::BAD_REQUEST,
+                    &ErrorResponse::new(
+                        "invalid_batch",
+                        format!(
+                            "Item {index} exceeds the limit of {} bytes",
+                            settings().body_limit
+                        ),
+                    ),
+                ));
+            }
+            let items = execute_batch::<N>(batch).await;
+            metrics().record_request("execute_batch", "success");
+            Ok::<_, Rejection>(warp::reply::json(&BatchResponse { items }).into_response())
+        })
+}
+
+// POST /execute/stream
+pub fn execute_stream_route<N: Network>(
+) -> impl Filter<Extract = impl Reply, Error = Rejection> + Clone {
+    let client = broadcast_client();
+    warp::post()
+        .and(accepting_work())
+        .and(warp::path!("execute" / "stream"))
+        .and(warp::body::content_length_limit(settings().body_limit))
+        .and(warp::body::bytes())
+        .and(warp::any().map(move || client.clone()))
+        .map(|request_bytes: Bytes, client: reqwest::Client| {
+            let (sender, receiver) = mpsc::unbounded_channel();
+            let _ = sender.send(ProgressEvent::Queued);
+            tokio::spawn(async move {
+                let progress = sender.clone();
+                let on_phase = move |phase: Phase| {
+                    let _ = progress.send(ProgressEvent::from(phase));
+                };
+                let event = match stream_execution::<N>(&client, request_bytes, on_phase).await {
+                    Ok((transaction, broadcast)) => {
+                        metrics().record_request("execute_stream", "success");
+                        ProgressEvent::Done {
+                            transaction,
+                            broadcast,
+                        }
+                    }
+                    Err(error) => {
+                        let outcome = match error.stage {
+                            Some(_) => "verification_failed",
+                            None => "error",
+                        };
+                        metrics().record_request("execute_stream", outcome);
+                        ProgressEvent::Failed { error }
+                    }
+                };
+                let _ = sender.send(event);
+            });
+            let events = UnboundedReceiverStream::new(receiver)
+                .map(|event| Event::default().event(event.name()).json_data(&event));
+            warp::sse::reply(warp::sse::keep_alive().stream(events))
+        })
+}
+
+/// Executes a streamed request through the cache, then forwards the transaction to the node
+/// if broadcasting is enabled, as `POST /execute` does.
+async fn stream_execution<N: Network>(
+    client: &reqwest::Client,
+    request_bytes: Bytes,
+    on_phase: impl Fn(Phase) + Send + Sync + 'static,
+) -> Result<(Vec<u8>, Option<BroadcastResponse>), ErrorResponse> {
+    let output = execute_once_with_progress::<N>(request_bytes, on_phase)
+        .await
+        .map_err(|error| execution_error(&error))?;
+    match &settings().broadcast {
+        Some(broadcast_settings) => {
+            let broadcast =
+                forward_transaction::<N>(client, broadcast_settings, output.transaction.clone())
+                    .await
+                    .map_err(|(_, error)| error)?;
+            Ok((output.transaction, Some(broadcast)))
+        }
+        None => Ok((output.transaction, None)),
+    }
+}
+
+/// Executes the request, reusing the result of an earlier or in-flight request with the same
+/// execution ID. A request that cannot be decoded fails with the decoding error, without
+/// executing.
+pub async fn execute_once<N: Network>(request_bytes: Bytes) -> Result<ExecuteOutput> {
+    execute_once_with_progress::<N>(request_bytes, |_| {}).await
+}
+
+/// Executes the request as `execute_once` does, calling `on_phase` as each phase completes.
+/// A result reused from an earlier request reports no phases.
+pub async fn execute_once_with_progress<N: Network>(
+    request_bytes: Bytes,
+    on_phase: impl Fn(Phase) + Send + Sync + 'static,
+) -> Result<ExecuteOutput> {
+    // Decode the execution ID on a worker, since deserializing the authorization is expensive.
+    let bytes = request_bytes.clone();
+    let execution_id = spawn_worker(metrics(), move || execution_id::<N>(&bytes)).await?;
+    let execute = move || {
+        spawn_worker(metrics(), move || {
+            execute_with_progress::<N>(request_bytes, &on_phase)
+        })
+    };
+    execution_cache()
+        .get_or_execute(execution_id, execute)
+        .await
+}
+
+/// Proves the execution and fee of an `ExecutionRequest`, reusing the result of an earlier or
+/// in-flight request for the same authorizations. A request that cannot be decoded fails with
+/// the decoding error, without executing.
+pub async fn execute_artifacts_once<N: Network>(request_bytes: Bytes) -> Result<ArtifactsOutput> {
+    // Decode the cache key on a worker, since deserializing the authorizations is expensive.
+    let bytes = request_bytes.clone();
+    let key = spawn_worker(metrics(), move || artifacts_cache_key::<N>(&bytes)).await?;
+    let execute = move || spawn_worker(metrics(), move || execute_artifacts::<N>(request_bytes));
+    artifacts_cache().get_or_execute(key, execute).await
+}
+
+/// Proves every item of the batch across the pool, returning the outcomes in request order.
+/// The transactions are not broadcast, even if broadcasting is enabled.
+pub async fn execute_batch<N: Network>(batch: BatchRequest) -> Vec<BatchItem> {
+    let handles = batch
+        .requests
+        .into_iter()
+        .map(|request| tokio::spawn(execute_once::<N>(Bytes::from(request))))
+        .collect::<Vec<_>>();
+
+    let mut items = Vec::with_capacity(handles.len());
+    for handle in handles {
+        let item = match handle.await {
+            Ok(Ok(output)) => BatchItem {
+                transaction: Some(output.transaction),
+                error: None,
+            },
+            Ok(Err(error)) => BatchItem {
+                transaction: None,
+                error: Some(execution_error(&error)),
+            },
+            Err(error) => BatchItem {
+                transaction: None,
+                error: Some(ErrorResponse::new("execution_failed", error)),
+            },
+        };
+        items.push(item);
+    }
+    items
+}
+
+/// Describes a failed execution, reporting the stage at which verification failed, if any.
+fn execution_error(error: &anyhow::Error) -> ErrorResponse {
+    match error.downcast_ref::<VerificationError>() {
+        Some(error) => ErrorResponse::from(error),
+        None => ErrorResponse::new("execution_failed", error),
+    }
+}
+
+/// Forwards the serialized transaction to the node and reports the node's reply.
+async fn broadcast_reply<N: Network>(
+    client: &reqwest::Client,
+    broadcast_settings: &BroadcastSettings,
+    transaction_bytes: Vec<u8>,
+) -> warp::reply::Response {
+    match forward_transaction::<N>(client, broadcast_settings, transaction_bytes).await {
+        Ok(response) => warp::reply::json(&response).into_response(),
+        Err((status, error)) => error_reply(status, &error),
+    }
+}
+
+/// Forwards the serialized transaction to the node, returning the node's reply, or the error
+/// along with the status code to report it with.
+async fn forward_transaction<N: Network>(
+    client: &reqwest::Client,
+    broadcast_settings: &BroadcastSettings,
+    transaction_bytes: Vec<u8>,
+) -> Result<BroadcastResponse, (StatusCode, ErrorResponse)> {
+    let transaction = match Transaction::<N>::from_bytes_le(&transaction_bytes) {
+        Ok(transaction) => transaction,
+        Err(error) => {
+            return Err((
+                StatusCode::INTERNAL_SERVER_ERROR,
+                ErrorResponse::new("invalid_transaction", error),
+            ))
+        }
+    };
+    match broadcast(client, broadcast_settings, &transaction).await {
+        Ok(reply) => {
+            metrics().record_request("broadcast", "success");
+            Ok(BroadcastResponse {
+                transaction_id: transaction.id().to_string(),
+                transaction: transaction_bytes,
+                node_status: reply.status,
+                node_response: reply.body,
+                attempts: reply.attempts,
+            })
+        }
+        Err(error) => {
+            metrics().record_request("broadcast", "error");
+            Err((
+                StatusCode::BAD_GATEWAY,
+                ErrorResponse::new("broadcast_failed", error),
+            ))
+        }
+    }
+}
+
+/// Constructs a JSON error reply with the given status code.
+fn error_reply(status: StatusCode, error: &ErrorResponse) -> warp::reply::Response {
+    warp::reply::with_status(warp::reply::json(error), status).into_response()
+}
+
 // GET /health
 pub fn health_route(
 ) -> impl Filter<Extract = (warp::reply::WithStatus<&'static str>,), Error = warp::Rejection> + Clone
@@ -49,5 +400,113 @@ pub fn health_route(
     warp::get()
         .and(warp::path("health"))
         .and(warp::path::end())
-        .map(|| warp::reply::with_status("Execute Service is up", warp::http::StatusCode::OK))
+        .map(|| match is_draining() {
+            true => warp::reply::with_status(
+                "Execute Service is shutting down",
+                warp::http::StatusCode::SERVICE_UNAVAILABLE,
+            ),
+            false => warp::reply::with_status("Execute Service is up", warp::http::StatusCode::OK),
+        })
+}
+
+// GET /health/live
+pub fn live_route() -> impl Filter<Extract = impl Reply, Error = Rejection> + Clone {
+    warp::get()
+        .and(warp::path!("health" / "live"))
+        .map(|| "Execute Service is alive")
+}
+
+// GET /health/ready
+pub fn ready_route<N: Network>() -> impl Filter<Extract = impl Reply, Error = Rejection> + Clone {
+    warp::get().and(warp::path!("health" / "ready")).map(|| {
+        let readiness = readiness::<N>();
+        let status = match readiness.ready {
+            true => StatusCode::OK,
+            false => StatusCode::SERVICE_UNAVAILABLE,
+        };
+        warp::reply::with_status(warp::reply::json(&readiness), status)
+    })
+}
+
+// GET /keys
+pub fn keys_route() -> impl Filter<Extract = impl Reply, Error = Rejection> + Clone {
+    warp::get()
+        .and(warp::path("keys"))
+        .and(warp::path::end())
+        .map(|| warp::reply::json(&resident_keys()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn test_execute_rejects_invalid_output_mode() {
+        let response = warp::test::request()
+            .method("POST")
+            .path("/execute?output=receipt")
+            .body(vec![1, 2, 3])
+            .reply(&execute_route::<TestnetV0>())
+            .await;
+        assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+    }
+
+    // Posts an undecodable request to `path`, expecting the structured execution error.
+    async fn check_execution_error<F>(path: &str, route: &F)
+    where
+        F: Filter + 'static,
+        F::Extract: Reply + Send,
+    {
+        let response = warp::test::request()
+            .method("POST")
+            .path(path)
+            .body(vec![1, 2, 3])
+            .reply(route)
+            .await;
+        assert_eq!(response.status(), StatusCode::UNPROCESSABLE_ENTITY);
+        let error: ErrorResponse = serde_json::from_slice(response.body()).unwrap();
+        assert_eq!(error.code, "execution_failed");
+    }
+
+    #[tokio::test]
+    async fn test_execute_reports_structured_error() {
+        check_execution_error("/execute", &execute_route::<TestnetV0>()).await;
+        check_execution_error("/execution", &execution_route::<TestnetV0>()).await;
+    }
+
+    #[tokio::test]
+    async fn test_batch_rejects_item_over_body_limit() {
+        let batch = BatchRequest {
+            requests: vec![vec![0; settings().body_limit as usize + 1]],
+        };
+        let response = warp::test::request()
+            .method("POST")
+            .path("/execute/batch")
+            .body(batch.to_bytes_le().unwrap())
+            .reply(&execute_batch_route::<TestnetV0>())
+            .await;
+        assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+        let error: ErrorResponse = serde_json::from_slice(response.body()).unwrap();
+        assert_eq!(error.code, "invalid_batch");
+    }
+
+    #[tokio::test]
+    async fn test_stream_reports_structured_error() {
+        let response = warp::test::request()
+            .method("POST")
+            .path("/execute/stream")
+            .body(vec![1, 2, 3])
+            .reply(&execute_stream_route::<TestnetV0>())
+            .await;
+        let body = String::from_utf8(response.body().to_vec()).unwrap();
+        let data = body
+            .lines()
+            .filter_map(|line| line.strip_prefix("data:"))
+            .last()
+            .unwrap();
+        match serde_json::from_str::<ProgressEvent>(data).unwrap() {
+            ProgressEvent::Failed { error } => assert_eq!(error.code, "execution_failed"),
+            event => panic!("Expected a failure, got {event:?}"),
+        }
+    }
 }
@@ -0,0 +1,98 @@
+// Copyright (C) 2019-2024 Aleo Systems Inc.
+// This file is part of the Aleo SDK library.
+
+// The Aleo SDK library is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// The Aleo SDK library is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with the Aleo SDK library. If not, see <https://www.gnu.org/licenses/>.
+
+use super::*;
+
+use std::str::FromStr;
+use std::sync::OnceLock;
+use std::time::Duration;
+
+/// How the function and the fee of a transaction are proved.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ProvingMode {
+    /// Prove the function and the fee at the same time on the rayon pool.
+    #[default]
+    Parallel,
+    /// Prove the fee after the function.
+    Sequential,
+}
+
+impl FromStr for ProvingMode {
+    type Err = anyhow::Error;
+
+    fn from_str(mode: &str) -> Result<Self> {
+        match mode {
+            "parallel" => Ok(Self::Parallel),
+            "sequential" => Ok(Self::Sequential),
+            _ => Err(anyhow!(
+                "Invalid proving mode '{mode}', expected 'parallel' or 'sequential'"
+            )),
+        }
+    }
+}
+
+/// The settings shared by every worker thread of the execute service.
+#[derive(Clone, Debug)]
+pub struct Settings {
+    /// The programs and functions whose proving keys are loaded when a worker starts.
+    /// Proving keys for any other function are loaded on first use.
+    pub preload: Vec<PreloadTarget>,
+    /// Whether the function and the fee are proved at the same time.
+    pub proving: ProvingMode,
+    /// Whether each transaction is verified against the verifying keys before it is returned.
+    pub verify: bool,
+    /// Where finished transactions are forwarded, if broadcasting is enabled.
+    pub broadcast: Option<BroadcastSettings>,
+    /// How long the result of an execution is returned for repeated requests with the same
+    /// execution ID. A zero duration disables the cache.
+    pub idempotency_ttl: Duration,
+    /// The maximum request body size in bytes.
+    pub body_limit: u64,
+    /// The maximum body size in bytes of a `POST /execute/batch` request.
+    pub batch_body_limit: u64,
+    /// The maximum number of items in a batch.
+    pub batch_max_items: usize,
+}
+
+impl Default for Settings {
+    fn default() -> Self {
+        Self {
+            preload: vec![PreloadTarget::program("credits.aleo")],
+            proving: ProvingMode::default(),
+            verify: false,
+            broadcast: None,
+            idempotency_ttl: Duration::from_secs(600),
+            body_limit: 32 * 1024,             // 32 KiB
+            batch_body_limit: 4 * 1024 * 1024, // 4 MiB
+            batch_max_items: 128,
+        }
+    }
+}
+
+static SETTINGS: OnceLock<Settings> = OnceLock::new();
+
+/// Installs the service settings. This must be called before the first request is served.
+pub fn set_settings(settings: Settings) -> Result<()> {
+    SETTINGS
+        .set(settings)
+        .map_err(|_| anyhow!("The settings are already initialized"))
+}
+
+/// Returns the service settings, falling back to the defaults if none were installed.
+pub fn settings() -> &'static Settings {
+    SETTINGS.get_or_init(Settings::default)
+}
@@ -0,0 +1,143 @@
+// Copyright (C) 2019-2024 Aleo Systems Inc.
+// This file is part of the Aleo SDK library.
+
+// The Aleo SDK library is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// The Aleo SDK library is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with the Aleo SDK library. If not, see <https://www.gnu.org/licenses/>.
+
+use super::*;
+
+use std::time::{Duration, Instant};
+
+/// A phase of an execution.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Phase {
+    /// `process.execute` on the function authorization.
+    Execute,
+    /// `trace.prepare` for the function, which runs the state query.
+    Prepare,
+    /// `trace.prove_execution`.
+    ProveExecution,
+    /// `process.execute` on the fee authorization.
+    FeeExecute,
+    /// `trace.prepare` for the fee.
+    FeePrepare,
+    /// `trace.prove_fee`.
+    ProveFee,
+}
+
+impl Phase {
+    /// The name of the phase, as reported in the `Server-Timing` header.
+    pub fn name(&self) -> &'static str {
+        match self {
+            Phase::Execute => "execute",
+            Phase::Prepare => "prepare",
+            Phase::ProveExecution => "prove_execution",
+            Phase::FeeExecute => "fee_execute",
+            Phase::FeePrepare => "fee_prepare",
+            Phase::ProveFee => "prove_fee",
+        }
+    }
+
+    /// The `phase` label of the duration histogram. Fee phases are told apart by their function label.
+    fn metric_label(&self) -> &'static str {
+        match self {
+            Phase::Execute | Phase::FeeExecute => "execute",
+            Phase::Prepare | Phase::FeePrepare => "prepare",
+            Phase::ProveExecution | Phase::ProveFee => "prove",
+        }
+    }
+
+    /// The field of the `execute` tracing span holding the duration of the phase.
+    fn span_field(&self) -> &'static str {
+        match self {
+            Phase::Execute => "execute_ms",
+            Phase::Prepare => "prepare_ms",
+            Phase::ProveExecution => "prove_execution_ms",
+            Phase::FeeExecute => "fee_execute_ms",
+            Phase::FeePrepare => "fee_prepare_ms",
+            Phase::ProveFee => "prove_fee_ms",
+        }
+    }
+}
+
+/// The time spent in each phase of an execution.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct PhaseTimings {
+    pub execute: Duration,
+    pub prepare: Duration,
+    pub prove_execution: Duration,
+    pub fee_execute: Duration,
+    pub fee_prepare: Duration,
+    pub prove_fee: Duration,
+}
+
+impl PhaseTimings {
+    /// Runs `f` as `phase` of the function at `locator`, recording its duration
+    /// in these timings, the phase histogram and the current tracing span.
+    /// A failed phase is not recorded in the histogram, so that its labels only
+    /// name functions the process could run.
+    pub fn time<N: Network, T>(
+        &mut self,
+        phase: Phase,
+        locator: &Locator<N>,
+        f: impl FnOnce() -> Result<T>,
+    ) -> Result<T> {
+        let start = Instant::now();
+        let output = f()?;
+        let elapsed = start.elapsed();
+
+        *self.get_mut(phase) = elapsed;
+        metrics().observe_execution_phase(phase.metric_label(), locator, elapsed);
+        tracing::Span::current().record(phase.span_field(), elapsed.as_millis() as u64);
+
+        Ok(output)
+    }
+
+    /// Returns the duration of each phase, in execution order.
+    pub fn phases(&self) -> [(Phase, Duration); 6] {
+        [
+            (Phase::Execute, self.execute),
+            (Phase::Prepare, self.prepare),
+            (Phase::ProveExecution, self.prove_execution),
+            (Phase::FeeExecute, self.fee_execute),
+            (Phase::FeePrepare, self.fee_prepare),
+            (Phase::ProveFee, self.prove_fee),
+        ]
+    }
+
+    /// Formats the timings as a `Server-Timing` header value, with durations in milliseconds.
+    pub fn to_server_timing(&self) -> String {
+        self.phases()
+            .iter()
+            .map(|(phase, duration)| {
+                format!(
+                    "{};dur={:.3}",
+                    phase.name(),
+                    duration.as_secs_f64() * 1000.0
+                )
+            })
+            .collect::<Vec<_>>()
+            .join(", ")
+    }
+
+    fn get_mut(&mut self, phase: Phase) -> &mut Duration {
+        match phase {
+            Phase::Execute => &mut self.execute,
+            Phase::Prepare => &mut self.prepare,
+            Phase::ProveExecution => &mut self.prove_execution,
+            Phase::FeeExecute => &mut self.fee_execute,
+            Phase::FeePrepare => &mut self.fee_prepare,
+            Phase::ProveFee => &mut self.prove_fee,
+        }
+    }
+}
@@ -0,0 +1,94 @@
+// Copyright (C) 2019-2024 Aleo Systems Inc.
+// This file is part of the Aleo SDK library.
+
+// The Aleo SDK library is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// The Aleo SDK library is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with the Aleo SDK library. If not, see <https://www.gnu.org/licenses/>.
+
+use super::*;
+
+use std::fmt;
+
+/// The check that rejected a transaction during self-verification.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum VerificationStage {
+    Execution,
+    Fee,
+    FeeAmount,
+}
+
+/// The error returned when a produced transaction fails self-verification.
+#[derive(Clone, Debug)]
+pub struct VerificationError {
+    pub stage: VerificationStage,
+    pub message: String,
+}
+
+impl VerificationError {
+    fn new(stage: VerificationStage, message: impl fmt::Display) -> Self {
+        Self {
+            stage,
+            message: message.to_string(),
+        }
+    }
+}
+
+impl fmt::Display for VerificationError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "Verification failed ({:?}): {}",
+            self.stage, self.message
+        )
+    }
+}
+
+impl std::error::Error for VerificationError {}
+
+/// Verifies the execution proof, the fee proof and the fee amount against the verifying keys
+/// held by the process, as the network would before accepting the transaction.
+pub fn verify_transaction<N: Network>(
+    process: &Process<N>,
+    execution: &Execution<N>,
+    fee: &Fee<N>,
+) -> Result<()> {
+    // Verify the execution proof.
+    verify_execution(process, execution)?;
+
+    // Verify the fee proof, which must be bound to this execution.
+    let execution_id = execution.to_execution_id()?;
+    process
+        .verify_fee(fee, execution_id)
+        .map_err(|error| VerificationError::new(VerificationStage::Fee, error))?;
+
+    // Ensure the base fee covers the cost of the execution.
+    let (minimum_base_fee, _) = execution_cost(process, execution)?;
+    let base_fee = *fee.base_amount()?;
+    if base_fee < minimum_base_fee {
+        return Err(VerificationError::new(
+            VerificationStage::FeeAmount,
+            format!("The base fee of {base_fee} is below the minimum of {minimum_base_fee} microcredits"),
+        )
+        .into());
+    }
+
+    Ok(())
+}
+
+/// Verifies the execution proof against the verifying keys held by the process.
+pub fn verify_execution<N: Network>(process: &Process<N>, execution: &Execution<N>) -> Result<()> {
+    process
+        .verify_execution(execution)
+        .map_err(|error| VerificationError::new(VerificationStage::Execution, error))?;
+    Ok(())
+}
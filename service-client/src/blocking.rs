@@ -0,0 +1,113 @@
+// Copyright (C) 2019-2024 Aleo Systems Inc.
+// This file is part of the Aleo SDK library.
+
+// The Aleo SDK library is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// The Aleo SDK library is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with the Aleo SDK library. If not, see <https://www.gnu.org/licenses/>.
+
+use super::*;
+
+use tokio::runtime::Runtime;
+
+/// A `ServiceClient` for callers without an async runtime. Each call blocks until it finishes.
+/// It must not be used from within an async runtime.
+#[derive(Debug)]
+pub struct BlockingServiceClient<N: Network> {
+    inner: ServiceClient<N>,
+    runtime: Runtime,
+}
+
+impl<N: Network> BlockingServiceClient<N> {
+    /// Creates a client with the given endpoints, timeout and retry policy.
+    pub fn new(config: ClientConfig) -> Result<Self, ServiceError> {
+        let runtime = tokio::runtime::Builder::new_current_thread()
+            .enable_all()
+            .build()
+            .map_err(ServiceError::Runtime)?;
+        Ok(Self {
+            inner: ServiceClient::new(config)?,
+            runtime,
+        })
+    }
+
+    /// Returns the async client this facade wraps.
+    pub fn inner(&self) -> &ServiceClient<N> {
+        &self.inner
+    }
+
+    /// See `ServiceClient::keygen`.
+    pub fn keygen(&self, seed: &Field<N>) -> Result<(PrivateKey<N>, Address<N>), ServiceError> {
+        self.runtime.block_on(self.inner.keygen(seed))
+    }
+
+    /// See `ServiceClient::authorize`.
+    pub fn authorize(
+        &self,
+        request: &AuthorizeRequest<N>,
+    ) -> Result<AuthorizeResponse<N>, ServiceError> {
+        self.runtime.block_on(self.inner.authorize(request))
+    }
+
+    /// See `ServiceClient::sign`.
+    pub fn sign(&self, request: &SignRequest<N>) -> Result<SignResponse, ServiceError> {
+        self.runtime.block_on(self.inner.sign(request))
+    }
+
+    /// See `ServiceClient::verify`.
+    pub fn verify(&self, request: &VerifyRequest<N>) -> Result<VerifyResponse, ServiceError> {
+        self.runtime.block_on(self.inner.verify(request))
+    }
+
+    /// See `ServiceClient::execute`.
+    pub fn execute(&self, request: &ExecuteRequest<N>) -> Result<ExecuteOutcome<N>, ServiceError> {
+        self.runtime.block_on(self.inner.execute(request))
+    }
+
+    /// See `ServiceClient::state_root`.
+    pub fn state_root(&self) -> Result<N::StateRoot, ServiceError> {
+        self.runtime.block_on(self.inner.state_root())
+    }
+
+    /// See `ServiceClient::program`.
+    pub fn program(&self, program_id: &ProgramID<N>) -> Result<Program<N>, ServiceError> {
+        self.runtime.block_on(self.inner.program(program_id))
+    }
+
+    /// See `ServiceClient::wait_for_confirmation`.
+    pub fn wait_for_confirmation(
+        &self,
+        transaction_id: &N::TransactionID,
+        timeout: std::time::Duration,
+        poll_interval: std::time::Duration,
+    ) -> Result<Confirmation, ServiceError> {
+        self.runtime.block_on(self.inner.wait_for_confirmation(
+            transaction_id,
+            timeout,
+            poll_interval,
+        ))
+    }
+
+    /// See `ServiceClient::execute_replicas`.
+    pub fn execute_replicas(&self) -> Vec<ReplicaStatus> {
+        self.inner.execute_replicas()
+    }
+
+    /// See `ServiceClient::check_execute_replicas`.
+    pub fn check_execute_replicas(&self) -> Vec<ReplicaStatus> {
+        self.runtime.block_on(self.inner.check_execute_replicas())
+    }
+
+    /// See `ServiceClient::broadcast`.
+    pub fn broadcast(&self, transaction: &Transaction<N>) -> Result<String, ServiceError> {
+        self.runtime.block_on(self.inner.broadcast(transaction))
+    }
+}
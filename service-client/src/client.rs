@@ -0,0 +1,624 @@
+// Copyright (C) 2019-2024 Aleo Systems Inc.
+// This file is part of the Aleo SDK library.
+
+// The Aleo SDK library is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// The Aleo SDK library is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with the Aleo SDK library. If not, see <https://www.gnu.org/licenses/>.
+
+use super::*;
+
+use authorize_service::KeygenResponse;
+use execute_service::BroadcastResponse;
+use reqwest::{RequestBuilder, Response};
+use std::marker::PhantomData;
+use std::sync::Arc;
+use std::time::Duration;
+
+/// The time allowed for a replica to answer a readiness probe.
+const PROBE_TIMEOUT: Duration = Duration::from_secs(5);
+
+/// A transaction proved by the execute service, and the node's answer if the service
+/// broadcast it.
+#[derive(Clone, Debug)]
+pub struct ExecuteOutcome<N: Network> {
+    pub transaction: Transaction<N>,
+    pub broadcast: Option<ServiceBroadcast>,
+}
+
+/// The node's answer to a broadcast made by the execute service.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ServiceBroadcast {
+    pub node_status: u16,
+    pub node_response: String,
+    pub attempts: u32,
+}
+
+impl ServiceBroadcast {
+    /// Whether the node accepted the transaction.
+    pub fn is_accepted(&self) -> bool {
+        (200..300).contains(&self.node_status)
+    }
+}
+
+/// A typed client for the authorize and execute services, and the node they rely on.
+#[derive(Clone, Debug)]
+pub struct ServiceClient<N: Network> {
+    config: ClientConfig,
+    pub(crate) http: reqwest::Client,
+    replicas: Arc<Replicas>,
+    _network: PhantomData<N>,
+}
+
+impl<N: Network> ServiceClient<N> {
+    /// Creates a client with the given endpoints, timeout and retry policy.
+    pub fn new(config: ClientConfig) -> Result<Self, ServiceError> {
+        if config.execute_urls.is_empty() {
+            return Err(ServiceError::Config(
+                "At least one execute service URL is required".to_string(),
+            ));
+        }
+        let http = reqwest::Client::builder().timeout(config.timeout).build()?;
+        let replicas = Arc::new(Replicas::new(&config.execute_urls));
+        Ok(Self {
+            config,
+            http,
+            replicas,
+            _network: PhantomData,
+        })
+    }
+
+    /// Returns the configuration of the client.
+    pub fn config(&self) -> &ClientConfig {
+        &self.config
+    }
+
+    /// Derives a private key and its address from the seed.
+    pub async fn keygen(
+        &self,
+        seed: &Field<N>,
+    ) -> Result<(PrivateKey<N>, Address<N>), ServiceError> {
+        let url = format!("{}/keygen/{}", self.config.authorize_url, **seed);
+        let response = self.send(|http| http.get(&url)).await?;
+        let response = response.json::<KeygenResponse>().await?;
+        Ok((
+            decode_bytes(&response.private_key)?,
+            decode_bytes(&response.address)?,
+        ))
+    }
+
+    /// Authorizes a function call and its fee.
+    pub async fn authorize(
+        &self,
+        request: &AuthorizeRequest<N>,
+    ) -> Result<AuthorizeResponse<N>, ServiceError> {
+        let url = format!("{}/authorize", self.config.authorize_url);
+        let response = self.send(|http| http.post(&url).json(request)).await?;
+        Ok(response.json().await?)
+    }
+
+    /// Signs a message with the private key of the request.
+    pub async fn sign(&self, request: &SignRequest<N>) -> Result<SignResponse, ServiceError> {
+        let url = format!("{}/sign", self.config.authorize_url);
+        let response = self.send(|http| http.post(&url).json(request)).await?;
+        Ok(response.json().await?)
+    }
+
+    /// Verifies a signature over a message.
+    pub async fn verify(&self, request: &VerifyRequest<N>) -> Result<VerifyResponse, ServiceError> {
+        let url = format!("{}/verify", self.config.authorize_url);
+        let response = self.send(|http| http.post(&url).json(request)).await?;
+        Ok(response.json().await?)
+    }
+
+    /// Proves the request into a transaction on an execute replica, along with the node's
+    /// answer if the service broadcast it.
+    /// The request only goes to another replica if the first did not take it, because each
+    /// replica caches its results separately and another one would prove it again. Set
+    /// `resend_execute` to also retry after a timeout or a server error.
+    pub async fn execute(
+        &self,
+        request: &ExecuteRequest<N>,
+    ) -> Result<ExecuteOutcome<N>, ServiceError> {
+        let body = request
+            .to_bytes_le()
+            .map_err(|error| ServiceError::Decode(error.to_string()))?;
+        let response = self
+            .send_to_replica("/execute", |http, url| {
+                http.post(url)
+                    .header("content-type", "application/octet-stream")
+                    .body(body.clone())
+            })
+            .await?;
+        // A service that broadcasts replies with JSON that carries the transaction.
+        let is_json = response
+            .headers()
+            .get("content-type")
+            .map_or(false, |value| {
+                value.as_bytes().starts_with(b"application/json")
+            });
+        let (bytes, broadcast) = match is_json {
+            true => {
+                let response = response.json::<BroadcastResponse>().await?;
+                let broadcast = ServiceBroadcast {
+                    node_status: response.node_status,
+                    node_response: response.node_response,
+                    attempts: response.attempts,
+                };
+                (response.transaction, Some(broadcast))
+            }
+            false => (response.bytes().await?.to_vec(), None),
+        };
+        Ok(ExecuteOutcome {
+            transaction: decode_bytes(&bytes)?,
+            broadcast,
+        })
+    }
+
+    /// Returns the state of each execute service replica.
+    pub fn execute_replicas(&self) -> Vec<ReplicaStatus> {
+        self.replicas.status()
+    }
+
+    /// Probes the readiness of every execute service replica, putting the replicas that are
+    /// not ready into cooldown and taking the ready ones out of it. Requests to `/execute`
+    /// probe the replicas first once every `cooldown`.
+    pub async fn check_execute_replicas(&self) -> Vec<ReplicaStatus> {
+        for index in 0..self.replicas.len() {
+            let url = format!("{}/health/ready", self.replicas.url(index));
+            match send_once(self.http.get(&url).timeout(PROBE_TIMEOUT)).await {
+                Ok(_) => self.replicas.mark_healthy(index),
+                Err(_) => self.replicas.mark_unhealthy(index, self.config.cooldown),
+            }
+        }
+        self.replicas.status()
+    }
+
+    /// Returns the latest state root of the node.
+    pub async fn state_root(&self) -> Result<N::StateRoot, ServiceError> {
+        let url = self.node_endpoint("stateRoot/latest");
+        let response = self.send(|http| http.get(&url)).await?;
+        Ok(response.json().await?)
+    }
+
+    /// Returns a program deployed on the node.
+    pub async fn program(&self, program_id: &ProgramID<N>) -> Result<Program<N>, ServiceError> {
+        let url = self.node_endpoint(&format!("program/{program_id}"));
+        let response = self.send(|http| http.get(&url)).await?;
+        Ok(response.json().await?)
+    }
+
+    /// Sends the transaction to the node, returning the node's response body.
+    pub async fn broadcast(&self, transaction: &Transaction<N>) -> Result<String, ServiceError> {
+        let url = self.node_endpoint("transaction/broadcast");
+        let response = self.send(|http| http.post(&url).json(transaction)).await?;
+        Ok(response.text().await?)
+    }
+
+    /// Returns the URL of a node endpoint, e.g. `stateRoot/latest`.
+    pub fn node_endpoint(&self, path: &str) -> String {
+        format!("{}/{}/{}", self.config.node_url, network_path::<N>(), path)
+    }
+
+    // Sends the request built by `build`, retrying retryable failures with a doubling backoff.
+    pub(crate) async fn send(
+        &self,
+        build: impl Fn(&reqwest::Client) -> RequestBuilder,
+    ) -> Result<Response, ServiceError> {
+        let mut backoff = self.config.initial_backoff;
+        let mut attempt = 1;
+        loop {
+            let result = send_once(build(&self.http)).await;
+            match result {
+                Err(error) if error.is_retryable() && attempt < self.config.max_attempts => {
+                    tokio::time::sleep(backoff).await;
+                    backoff = self.next_backoff(backoff);
+                    attempt += 1;
+                }
+                result => return result,
+            }
+        }
+    }
+
+    // Sends the request built by `build` for `path` to an execute replica, probing the replicas
+    // first if they are due. A replica that did not take the request is put into cooldown, and
+    // the request is retried on another replica, or on the same one after a doubling backoff
+    // once every replica has failed it. Other retryable failures are only retried if
+    // `resend_execute` is set.
+    pub(crate) async fn send_to_replica(
+        &self,
+        path: &str,
+        build: impl Fn(&reqwest::Client, &str) -> RequestBuilder,
+    ) -> Result<Response, ServiceError> {
+        if self.replicas.probe_due(self.config.cooldown) {
+            self.check_execute_replicas().await;
+        }
+        let mut backoff = self.config.initial_backoff;
+        let mut attempt = 1;
+        let mut tried = Vec::new();
+        loop {
+            if tried.len() == self.replicas.len() {
+                tried.clear();
+                tokio::time::sleep(backoff).await;
+                backoff = self.next_backoff(backoff);
+            }
+            let index = self.replicas.pick(&tried);
+            let result = {
+                let _outstanding = self.replicas.start(index);
+                let url = format!("{}{path}", self.replicas.url(index));
+                send_once(build(&self.http, &url)).await
+            };
+            match result {
+                Err(error) if self.is_resendable(&error) => {
+                    if error.is_unavailable() {
+                        self.replicas.mark_unhealthy(index, self.config.cooldown);
+                    }
+                    if attempt >= self.config.max_attempts {
+                        return Err(error);
+                    }
+                    tried.push(index);
+                    attempt += 1;
+                }
+                result => {
+                    // The replica answered, even if the request itself failed.
+                    if !matches!(
+                        result,
+                        Err(ServiceError::Timeout | ServiceError::Transport(_))
+                    ) {
+                        self.replicas.mark_healthy(index);
+                    }
+                    return result;
+                }
+            }
+        }
+    }
+
+    // Whether a failed `/execute` attempt may be sent again.
+    fn is_resendable(&self, error: &ServiceError) -> bool {
+        match self.config.resend_execute {
+            true => error.is_retryable(),
+            false => error.is_unavailable(),
+        }
+    }
+
+    // Returns the delay after `backoff`, doubled and capped at `max_backoff`.
+    fn next_backoff(&self, backoff: Duration) -> Duration {
+        backoff.saturating_mul(2).min(self.config.max_backoff)
+    }
+}
+
+// Sends the request once, returning the response if it succeeded.
+async fn send_once(request: RequestBuilder) -> Result<Response, ServiceError> {
+    match request.send().await {
+        Ok(response) => check_status(response).await,
+        Err(error) => Err(ServiceError::from(error)),
+    }
+}
+
+// Returns the response if it succeeded, or the service error it carries otherwise.
+async fn check_status(response: Response) -> Result<Response, ServiceError> {
+    let status = response.status();
+    match status.is_success() {
+        true => Ok(response),
+        false => {
+            let body = response.text().await.unwrap_or_default();
+            Err(ServiceError::from_response(status.as_u16(), &body))
+        }
+    }
+}
+
+// Decodes a value serialized by a service.
+fn decode_bytes<T: FromBytes>(bytes: &[u8]) -> Result<T, ServiceError> {
+    T::from_bytes_le(bytes).map_err(|error| ServiceError::Decode(error.to_string()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use rand_chacha::rand_core::SeedableRng;
+    use snarkvm::prelude::TestnetV0;
+    use std::sync::atomic::{AtomicU32, Ordering};
+    use std::sync::Arc;
+    use std::time::Duration;
+    use warp::{http::StatusCode, Filter};
+
+    type CurrentNetwork = TestnetV0;
+
+    // Starts a stub authorize service that answers the n-th sign attempt with `reply(n)`.
+    fn stub_service(
+        reply: impl Fn(u32) -> (StatusCode, serde_json::Value) + Clone + Send + Sync + 'static,
+    ) -> (ServiceClient<CurrentNetwork>, Arc<AtomicU32>) {
+        let attempts = Arc::new(AtomicU32::new(0));
+        let counter = attempts.clone();
+        let route = warp::post()
+            .and(warp::path("sign"))
+            .and(warp::body::bytes())
+            .map(move |_| {
+                let attempt = counter.fetch_add(1, Ordering::SeqCst) + 1;
+                let (status, body) = reply(attempt);
+                warp::reply::with_status(warp::reply::json(&body), status)
+            });
+        let (address, server) = warp::serve(route).bind_ephemeral(([127, 0, 0, 1], 0));
+        tokio::spawn(server);
+        let client = ServiceClient::new(ClientConfig {
+            authorize_url: format!("http://{address}"),
+            initial_backoff: Duration::from_millis(10),
+            ..Default::default()
+        })
+        .unwrap();
+        (client, attempts)
+    }
+
+    fn sign_request() -> SignRequest<CurrentNetwork> {
+        let rng = &mut rand_chacha::ChaCha20Rng::seed_from_u64(0);
+        SignRequest {
+            private_key: PrivateKey::new(rng).unwrap(),
+            message: b"message".to_vec(),
+        }
+    }
+
+    #[tokio::test]
+    async fn test_retries_unavailable_service() {
+        let (client, attempts) = stub_service(|attempt| match attempt {
+            1 => (
+                StatusCode::SERVICE_UNAVAILABLE,
+                serde_json::json!("draining"),
+            ),
+            _ => (
+                StatusCode::OK,
+                serde_json::json!({ "signed_message": [1, 2, 3] }),
+            ),
+        });
+        let response = client.sign(&sign_request()).await.unwrap();
+        assert_eq!(response.signed_message, vec![1, 2, 3]);
+        assert_eq!(attempts.load(Ordering::SeqCst), 2);
+    }
+
+    #[tokio::test]
+    async fn test_returns_typed_service_errors() {
+        let (client, attempts) = stub_service(|_| {
+            (
+                StatusCode::UNPROCESSABLE_ENTITY,
+                serde_json::json!({
+                    "code": "verification_failed",
+                    "message": "bad proof",
+                    "stage": "fee",
+                }),
+            )
+        });
+        match client.sign(&sign_request()).await.unwrap_err() {
+            ServiceError::Service {
+                status,
+                code,
+                message,
+                stage,
+            } => {
+                assert_eq!(status, 422);
+                assert_eq!(code, ErrorCode::VerificationFailed);
+                assert_eq!(message, "bad proof");
+                assert_eq!(stage.as_deref(), Some("fee"));
+            }
+            error => panic!("Unexpected error: {error}"),
+        }
+        assert_eq!(attempts.load(Ordering::SeqCst), 1);
+    }
+
+    // Starts a stub execute replica that answers every request with `status`, counting them.
+    fn stub_replica(status: StatusCode) -> (String, Arc<AtomicU32>) {
+        stub_replica_with_readiness(StatusCode::OK, status)
+    }
+
+    // Starts a stub execute replica whose readiness probe answers with `ready`.
+    fn stub_replica_with_readiness(
+        ready: StatusCode,
+        status: StatusCode,
+    ) -> (String, Arc<AtomicU32>) {
+        let hits = Arc::new(AtomicU32::new(0));
+        let counter = hits.clone();
+        let execute = warp::post().and(warp::path("execute")).map(move || {
+            counter.fetch_add(1, Ordering::SeqCst);
+            warp::reply::with_status(warp::reply::json(&"replica"), status)
+        });
+        let readiness = warp::get()
+            .and(warp::path!("health" / "ready"))
+            .map(move || warp::reply::with_status(warp::reply::json(&"readiness"), ready));
+        let (address, server) =
+            warp::serve(execute.or(readiness)).bind_ephemeral(([127, 0, 0, 1], 0));
+        tokio::spawn(server);
+        (format!("http://{address}"), hits)
+    }
+
+    fn replica_client(execute_urls: Vec<String>) -> ServiceClient<CurrentNetwork> {
+        ServiceClient::new(ClientConfig {
+            execute_urls,
+            initial_backoff: Duration::from_millis(10),
+            ..Default::default()
+        })
+        .unwrap()
+    }
+
+    #[tokio::test]
+    async fn test_fails_over_to_another_replica() {
+        let (unavailable, unavailable_hits) = stub_replica(StatusCode::SERVICE_UNAVAILABLE);
+        let (available, available_hits) = stub_replica(StatusCode::OK);
+        let client = replica_client(vec![unavailable.clone(), available]);
+
+        let send = || client.send_to_replica("/execute", |http, url| http.post(url));
+        send().await.unwrap();
+        assert_eq!(unavailable_hits.load(Ordering::SeqCst), 1);
+        assert_eq!(available_hits.load(Ordering::SeqCst), 1);
+
+        // The failed replica cools down, so the next request goes straight to the other one.
+        let status = client.execute_replicas();
+        assert_eq!(status[0].url, unavailable);
+        assert!(!status[0].healthy);
+        assert!(status[1].healthy);
+        send().await.unwrap();
+        assert_eq!(unavailable_hits.load(Ordering::SeqCst), 1);
+        assert_eq!(available_hits.load(Ordering::SeqCst), 2);
+    }
+
+    #[tokio::test]
+    async fn test_does_not_fail_over_rejected_requests() {
+        let (rejecting, rejecting_hits) = stub_replica(StatusCode::BAD_REQUEST);
+        let (available, available_hits) = stub_replica(StatusCode::OK);
+        let client = replica_client(vec![rejecting, available]);
+
+        let error = client
+            .send_to_replica("/execute", |http, url| http.post(url))
+            .await
+            .unwrap_err();
+        assert_eq!(error.code(), Some(&ErrorCode::Rejected));
+        assert_eq!(rejecting_hits.load(Ordering::SeqCst), 1);
+        assert_eq!(available_hits.load(Ordering::SeqCst), 0);
+        assert!(client.execute_replicas()[0].healthy);
+    }
+
+    #[tokio::test]
+    async fn test_probes_replicas_before_the_first_pick() {
+        let (not_ready, not_ready_hits) =
+            stub_replica_with_readiness(StatusCode::SERVICE_UNAVAILABLE, StatusCode::OK);
+        let (ready, ready_hits) = stub_replica(StatusCode::OK);
+        let client = replica_client(vec![not_ready, ready]);
+
+        client
+            .send_to_replica("/execute", |http, url| http.post(url))
+            .await
+            .unwrap();
+        assert_eq!(not_ready_hits.load(Ordering::SeqCst), 0);
+        assert_eq!(ready_hits.load(Ordering::SeqCst), 1);
+        assert!(!client.execute_replicas()[0].healthy);
+    }
+
+    #[tokio::test]
+    async fn test_fails_over_server_errors_only_if_asked() {
+        let (failing, failing_hits) = stub_replica(StatusCode::INTERNAL_SERVER_ERROR);
+        let (available, available_hits) = stub_replica(StatusCode::OK);
+        let send = |client: ServiceClient<CurrentNetwork>| async move {
+            client
+                .send_to_replica("/execute", |http, url| http.post(url))
+                .await
+        };
+
+        // The failing replica may have started proving, so the request is not sent again.
+        let client = replica_client(vec![failing.clone(), available.clone()]);
+        let error = send(client).await.unwrap_err();
+        assert!(matches!(error, ServiceError::Service { status: 500, .. }));
+        assert_eq!(failing_hits.load(Ordering::SeqCst), 1);
+        assert_eq!(available_hits.load(Ordering::SeqCst), 0);
+
+        let client = ServiceClient::new(ClientConfig {
+            execute_urls: vec![failing, available],
+            initial_backoff: Duration::from_millis(10),
+            resend_execute: true,
+            ..Default::default()
+        })
+        .unwrap();
+        send(client).await.unwrap();
+        assert_eq!(failing_hits.load(Ordering::SeqCst), 2);
+        assert_eq!(available_hits.load(Ordering::SeqCst), 1);
+    }
+
+    #[test]
+    fn test_requires_an_execute_url() {
+        let error = ServiceClient::<CurrentNetwork>::new(ClientConfig {
+            execute_urls: Vec::new(),
+            ..Default::default()
+        })
+        .unwrap_err();
+        assert!(matches!(error, ServiceError::Config(_)));
+    }
+
+    #[tokio::test]
+    async fn test_does_not_retry_failed_broadcasts() {
+        let (client, attempts) = stub_service(|_| {
+            (
+                StatusCode::BAD_GATEWAY,
+                serde_json::json!({ "code": "broadcast_failed", "message": "node is down" }),
+            )
+        });
+        let error = client.sign(&sign_request()).await.unwrap_err();
+        assert_eq!(error.code(), Some(&ErrorCode::BroadcastFailed));
+        assert_eq!(attempts.load(Ordering::SeqCst), 1);
+    }
+
+    #[tokio::test]
+    async fn test_does_not_resend_timed_out_executions() {
+        let hits = Arc::new(AtomicU32::new(0));
+        let counter = hits.clone();
+        let route = warp::post().and(warp::path("execute")).then(move || {
+            counter.fetch_add(1, Ordering::SeqCst);
+            async {
+                tokio::time::sleep(Duration::from_secs(5)).await;
+                warp::reply()
+            }
+        });
+        let (address, server) = warp::serve(route).bind_ephemeral(([127, 0, 0, 1], 0));
+        tokio::spawn(server);
+        let client = ServiceClient::<CurrentNetwork>::new(ClientConfig {
+            execute_urls: vec![format!("http://{address}")],
+            timeout: Duration::from_millis(100),
+            initial_backoff: Duration::from_millis(10),
+            ..Default::default()
+        })
+        .unwrap();
+
+        let error = client
+            .send_to_replica("/execute", |http, url| http.post(url))
+            .await
+            .unwrap_err();
+        assert!(matches!(error, ServiceError::Timeout));
+        assert_eq!(hits.load(Ordering::SeqCst), 1);
+    }
+
+    #[test]
+    fn test_service_broadcast_is_accepted_on_success_only() {
+        let broadcast = |node_status| ServiceBroadcast {
+            node_status,
+            node_response: String::new(),
+            attempts: 1,
+        };
+        assert!(broadcast(200).is_accepted());
+        assert!(!broadcast(409).is_accepted());
+        assert!(!broadcast(500).is_accepted());
+    }
+
+    #[test]
+    fn test_backoff_is_capped() {
+        let client = replica_client(vec!["http://localhost:8081".to_string()]);
+        assert_eq!(
+            client.next_backoff(Duration::from_millis(10)),
+            Duration::from_millis(20)
+        );
+        assert_eq!(
+            client.next_backoff(Duration::from_secs(20)),
+            client.config().max_backoff
+        );
+        assert_eq!(
+            client.next_backoff(Duration::MAX),
+            client.config().max_backoff
+        );
+    }
+
+    #[tokio::test]
+    async fn test_gives_up_after_max_attempts() {
+        let (client, attempts) = stub_service(|_| {
+            (
+                StatusCode::SERVICE_UNAVAILABLE,
+                serde_json::json!("draining"),
+            )
+        });
+        let error = client.sign(&sign_request()).await.unwrap_err();
+        assert_eq!(error.code(), Some(&ErrorCode::Unavailable));
+        assert_eq!(attempts.load(Ordering::SeqCst), 3);
+    }
+}
@@ -0,0 +1,73 @@
+// Copyright (C) 2019-2024 Aleo Systems Inc.
+// This file is part of the Aleo SDK library.
+
+// The Aleo SDK library is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// The Aleo SDK library is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with the Aleo SDK library. If not, see <https://www.gnu.org/licenses/>.
+
+use super::*;
+
+use std::time::Duration;
+
+/// Where the services and the node live, and how patiently to talk to them.
+#[derive(Clone, Debug)]
+pub struct ClientConfig {
+    /// The base URL of the authorize service, e.g. `http://localhost:8080`.
+    pub authorize_url: String,
+    /// The base URLs of the execute service replicas, e.g. `http://localhost:8081`.
+    /// Each request goes to a healthy replica with the fewest requests in flight.
+    pub execute_urls: Vec<String>,
+    /// The base URL of the node, e.g. `http://localhost:3030`.
+    pub node_url: String,
+    /// The time allowed for each attempt of a request, including reading the response.
+    pub timeout: Duration,
+    /// The maximum number of attempts per request.
+    pub max_attempts: u32,
+    /// The delay before the first retry, doubled after each failed attempt up to `max_backoff`.
+    pub initial_backoff: Duration,
+    /// The longest delay between two attempts.
+    pub max_backoff: Duration,
+    /// Whether `/execute` is sent again after a timeout or a server error other than 503.
+    /// The replica may have started proving before it failed, and each replica caches its
+    /// results separately, so sending it again can prove the same request twice.
+    pub resend_execute: bool,
+    /// How long an execute replica is skipped after it could not take a request, and how often
+    /// the readiness of the replicas is probed.
+    pub cooldown: Duration,
+}
+
+impl Default for ClientConfig {
+    fn default() -> Self {
+        Self {
+            authorize_url: "http://localhost:8080".to_string(),
+            execute_urls: vec!["http://localhost:8081".to_string()],
+            node_url: "http://localhost:3030".to_string(),
+            // Proving a transaction can take minutes on a busy service.
+            timeout: Duration::from_secs(600),
+            max_attempts: 3,
+            initial_backoff: Duration::from_millis(500),
+            max_backoff: Duration::from_secs(30),
+            resend_execute: false,
+            cooldown: Duration::from_secs(30),
+        }
+    }
+}
+
+/// Returns the path segment the node uses for the network, e.g. `mainnet`.
+pub fn network_path<N: Network>() -> &'static str {
+    match N::ID {
+        MainnetV0::ID => "mainnet",
+        TestnetV0::ID => "testnet",
+        CanaryV0::ID => "canary",
+        _ => panic!("Invalid network"),
+    }
+}
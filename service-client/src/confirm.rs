@@ -0,0 +1,317 @@
+// Copyright (C) 2019-2024 Aleo Systems Inc.
+// This file is part of the Aleo SDK library.
+
+// The Aleo SDK library is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// The Aleo SDK library is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with the Aleo SDK library. If not, see <https://www.gnu.org/licenses/>.
+
+use super::*;
+
+use std::time::{Duration, Instant};
+
+/// Whether a confirmed transaction was accepted or rejected. A rejected execution still
+/// pays its fee.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ConfirmationStatus {
+    Accepted,
+    Rejected,
+}
+
+/// Where and how a transaction was confirmed.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Confirmation {
+    pub transaction_id: String,
+    pub status: ConfirmationStatus,
+    pub block_hash: String,
+    pub block_height: u32,
+}
+
+/// What the node reports for a transaction in one poll.
+enum Poll {
+    Confirmed(Confirmation),
+    Pending,
+    Unknown,
+}
+
+/// The part of the node's confirmed transaction that reports the outcome.
+#[derive(Deserialize)]
+struct ConfirmedTransaction {
+    status: ConfirmationStatus,
+}
+
+impl<N: Network> ServiceClient<N> {
+    /// Polls the node until the transaction is confirmed or `timeout` expires, including the
+    /// time spent waiting on the node. After the timeout, a transaction the node still holds is
+    /// reported as `Unconfirmed`, and one it does not know as `Dropped`. A node that fails to
+    /// answer a poll fails the wait.
+    pub async fn wait_for_confirmation(
+        &self,
+        transaction_id: &N::TransactionID,
+        timeout: Duration,
+        poll_interval: Duration,
+    ) -> Result<Confirmation, ServiceError> {
+        let start = Instant::now();
+        // Whether the node knew the transaction at the last poll.
+        let mut known = true;
+        let unconfirmed = |known: bool| match known {
+            true => ServiceError::Unconfirmed {
+                transaction_id: transaction_id.to_string(),
+                waited: start.elapsed(),
+            },
+            false => ServiceError::Dropped {
+                transaction_id: transaction_id.to_string(),
+                waited: start.elapsed(),
+            },
+        };
+        loop {
+            let remaining = timeout.saturating_sub(start.elapsed());
+            match tokio::time::timeout(remaining, self.poll(transaction_id)).await {
+                Ok(Ok(Poll::Confirmed(confirmation))) => return Ok(confirmation),
+                Ok(Ok(Poll::Pending)) => known = true,
+                Ok(Ok(Poll::Unknown)) => known = false,
+                Ok(Err(error)) => return Err(error),
+                Err(_) => return Err(unconfirmed(known)),
+            }
+            if start.elapsed() + poll_interval > timeout {
+                return Err(unconfirmed(known));
+            }
+            tokio::time::sleep(poll_interval).await;
+        }
+    }
+
+    // Returns the confirmation of the transaction or, until there is one, whether the node
+    // holds the transaction.
+    async fn poll(&self, transaction_id: &N::TransactionID) -> Result<Poll, ServiceError> {
+        if let Some(confirmation) = self.confirmation(transaction_id).await? {
+            return Ok(Poll::Confirmed(confirmation));
+        }
+        // The node answers with 404 for a transaction it does not hold.
+        let url = self.node_endpoint(&format!("transaction/{transaction_id}"));
+        match self.send(|http| http.get(&url)).await {
+            Ok(_) => Ok(Poll::Pending),
+            Err(ServiceError::Service { status: 404, .. }) => Ok(Poll::Unknown),
+            Err(error) => Err(error),
+        }
+    }
+
+    /// Returns the confirmation of the transaction, or `None` if the node has not confirmed it yet.
+    pub async fn confirmation(
+        &self,
+        transaction_id: &N::TransactionID,
+    ) -> Result<Option<Confirmation>, ServiceError> {
+        // The node answers with 404 until the transaction is in a block.
+        let url = self.node_endpoint(&format!("transaction/confirmed/{transaction_id}"));
+        let confirmed = match self.send(|http| http.get(&url)).await {
+            Ok(response) => response.json::<ConfirmedTransaction>().await?,
+            Err(ServiceError::Service { status: 404, .. }) => return Ok(None),
+            Err(error) => return Err(error),
+        };
+
+        // Find the block holding the transaction.
+        let url = self.node_endpoint(&format!("find/blockHash/{transaction_id}"));
+        let block_hash = self
+            .send(|http| http.get(&url))
+            .await?
+            .json::<String>()
+            .await?;
+        let url = self.node_endpoint(&format!("height/{block_hash}"));
+        let block_height = self
+            .send(|http| http.get(&url))
+            .await?
+            .json::<u32>()
+            .await?;
+
+        Ok(Some(Confirmation {
+            transaction_id: transaction_id.to_string(),
+            status: confirmed.status,
+            block_hash,
+            block_height,
+        }))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use snarkvm::prelude::TestnetV0;
+    use std::sync::atomic::{AtomicU32, Ordering};
+    use std::sync::Arc;
+    use warp::{http::StatusCode, Filter};
+
+    type CurrentNetwork = TestnetV0;
+
+    // Starts a mock node that confirms every transaction with `status` from the n-th poll on,
+    // and until then holds it only if `known`.
+    fn mock_node(
+        confirmed_from: u32,
+        status: &'static str,
+        known: bool,
+    ) -> ServiceClient<CurrentNetwork> {
+        let polls = Arc::new(AtomicU32::new(0));
+        let confirmed =
+            warp::path!("testnet" / "transaction" / "confirmed" / String).map(move |_: String| {
+                let poll = polls.fetch_add(1, Ordering::SeqCst) + 1;
+                match poll >= confirmed_from {
+                    true => warp::reply::with_status(
+                        warp::reply::json(&serde_json::json!({
+                            "status": status,
+                            "type": "execute",
+                            "index": 0,
+                        })),
+                        StatusCode::OK,
+                    ),
+                    false => warp::reply::with_status(
+                        warp::reply::json(&"Missing transaction"),
+                        StatusCode::NOT_FOUND,
+                    ),
+                }
+            });
+        let transaction =
+            warp::path!("testnet" / "transaction" / String).map(move |_: String| match known {
+                true => warp::reply::with_status(
+                    warp::reply::json(&serde_json::json!({})),
+                    StatusCode::OK,
+                ),
+                false => warp::reply::with_status(
+                    warp::reply::json(&"Missing transaction"),
+                    StatusCode::NOT_FOUND,
+                ),
+            });
+        let block_hash = warp::path!("testnet" / "find" / "blockHash" / String)
+            .map(|_: String| warp::reply::json(&"ab1mock"));
+        let height = warp::path!("testnet" / "height" / String).map(|hash: String| {
+            assert_eq!(hash, "ab1mock");
+            warp::reply::json(&42u32)
+        });
+        let routes = warp::get().and(confirmed.or(transaction).or(block_hash).or(height));
+        let (address, server) = warp::serve(routes).bind_ephemeral(([127, 0, 0, 1], 0));
+        tokio::spawn(server);
+        ServiceClient::new(ClientConfig {
+            node_url: format!("http://{address}"),
+            ..Default::default()
+        })
+        .unwrap()
+    }
+
+    fn transaction_id() -> <CurrentNetwork as Network>::TransactionID {
+        Field::from_u64(7).into()
+    }
+
+    #[tokio::test]
+    async fn test_waits_until_accepted() {
+        let client = mock_node(3, "accepted", true);
+        let confirmation = client
+            .wait_for_confirmation(
+                &transaction_id(),
+                Duration::from_secs(5),
+                Duration::from_millis(10),
+            )
+            .await
+            .unwrap();
+        assert_eq!(confirmation.status, ConfirmationStatus::Accepted);
+        assert_eq!(confirmation.block_hash, "ab1mock");
+        assert_eq!(confirmation.block_height, 42);
+        assert_eq!(confirmation.transaction_id, transaction_id().to_string());
+    }
+
+    #[tokio::test]
+    async fn test_reports_rejected_transactions() {
+        let client = mock_node(1, "rejected", true);
+        let confirmation = client
+            .wait_for_confirmation(
+                &transaction_id(),
+                Duration::from_secs(5),
+                Duration::from_millis(10),
+            )
+            .await
+            .unwrap();
+        assert_eq!(confirmation.status, ConfirmationStatus::Rejected);
+    }
+
+    #[tokio::test]
+    async fn test_times_out_on_pending_transactions() {
+        let client = mock_node(u32::MAX, "accepted", true);
+        let error = client
+            .wait_for_confirmation(
+                &transaction_id(),
+                Duration::from_millis(100),
+                Duration::from_millis(10),
+            )
+            .await
+            .unwrap_err();
+        assert!(matches!(error, ServiceError::Unconfirmed { .. }));
+    }
+
+    #[tokio::test]
+    async fn test_reports_dropped_transactions() {
+        let client = mock_node(u32::MAX, "accepted", false);
+        let error = client
+            .wait_for_confirmation(
+                &transaction_id(),
+                Duration::from_millis(100),
+                Duration::from_millis(10),
+            )
+            .await
+            .unwrap_err();
+        assert!(matches!(error, ServiceError::Dropped { .. }));
+    }
+
+    // Starts a node whose confirmation endpoint answers every poll with `status` after `delay`.
+    fn failing_node(status: StatusCode, delay: Duration) -> ServiceClient<CurrentNetwork> {
+        let confirmed = warp::path!("testnet" / "transaction" / "confirmed" / String).then(
+            move |_: String| async move {
+                tokio::time::sleep(delay).await;
+                warp::reply::with_status(warp::reply::json(&"Node error"), status)
+            },
+        );
+        let (address, server) = warp::serve(confirmed).bind_ephemeral(([127, 0, 0, 1], 0));
+        tokio::spawn(server);
+        ServiceClient::new(ClientConfig {
+            node_url: format!("http://{address}"),
+            max_attempts: 1,
+            ..Default::default()
+        })
+        .unwrap()
+    }
+
+    #[tokio::test]
+    async fn test_returns_node_errors() {
+        let client = failing_node(StatusCode::INTERNAL_SERVER_ERROR, Duration::ZERO);
+        let error = client
+            .wait_for_confirmation(
+                &transaction_id(),
+                Duration::from_secs(5),
+                Duration::from_millis(10),
+            )
+            .await
+            .unwrap_err();
+        assert!(matches!(error, ServiceError::Service { status: 500, .. }));
+    }
+
+    #[tokio::test]
+    async fn test_bounds_each_poll_by_the_timeout() {
+        let client = failing_node(StatusCode::NOT_FOUND, Duration::from_secs(30));
+        let start = Instant::now();
+        let error = client
+            .wait_for_confirmation(
+                &transaction_id(),
+                Duration::from_millis(100),
+                Duration::from_millis(10),
+            )
+            .await
+            .unwrap_err();
+        assert!(matches!(error, ServiceError::Unconfirmed { .. }));
+        assert!(start.elapsed() < Duration::from_secs(5));
+    }
+}
@@ -0,0 +1,210 @@
+// Copyright (C) 2019-2024 Aleo Systems Inc.
+// This file is part of the Aleo SDK library.
+
+// The Aleo SDK library is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// The Aleo SDK library is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with the Aleo SDK library. If not, see <https://www.gnu.org/licenses/>.
+
+use super::*;
+
+use std::fmt;
+
+/// The error codes reported by the services, with the conditions they signal without a code.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum ErrorCode {
+    /// The produced transaction failed self-verification.
+    VerificationFailed,
+    /// The service could not compute the transaction.
+    ExecutionFailed,
+    /// The service produced a transaction it could not decode.
+    InvalidTransaction,
+    /// The service could not forward the transaction to the node.
+    BroadcastFailed,
+    /// The batch body is malformed.
+    InvalidBatch,
+    /// The batch has too many items.
+    BatchTooLarge,
+    /// The service is shutting down or overloaded.
+    Unavailable,
+    /// The service rejected the request without a code, e.g. because it could not be parsed.
+    Rejected,
+    /// A code this client does not know.
+    Other(String),
+}
+
+impl ErrorCode {
+    /// Parses a code from an error body, e.g. `verification_failed`.
+    pub fn from_code(code: &str) -> Self {
+        match code {
+            "verification_failed" => Self::VerificationFailed,
+            "execution_failed" => Self::ExecutionFailed,
+            "invalid_transaction" => Self::InvalidTransaction,
+            "broadcast_failed" => Self::BroadcastFailed,
+            "invalid_batch" => Self::InvalidBatch,
+            "batch_too_large" => Self::BatchTooLarge,
+            code => Self::Other(code.to_string()),
+        }
+    }
+}
+
+/// The JSON body of a service error.
+#[derive(Clone, Debug, Deserialize)]
+struct ErrorBody {
+    code: String,
+    message: String,
+    #[serde(default)]
+    stage: Option<String>,
+}
+
+/// An error returned by a `ServiceClient`.
+#[derive(Debug)]
+pub enum ServiceError {
+    /// The service answered with an error status.
+    Service {
+        status: u16,
+        code: ErrorCode,
+        message: String,
+        /// The check that failed, for `VerificationFailed`.
+        stage: Option<String>,
+    },
+    /// An attempt did not finish within the configured timeout.
+    Timeout,
+    /// The request could not be sent or the response could not be read.
+    Transport(reqwest::Error),
+    /// The response could not be decoded.
+    Decode(String),
+    /// The node holds the transaction but did not confirm it in time.
+    Unconfirmed {
+        transaction_id: String,
+        waited: std::time::Duration,
+    },
+    /// The node neither confirmed the transaction in time nor holds it; it was dropped or
+    /// never reached the node.
+    Dropped {
+        transaction_id: String,
+        waited: std::time::Duration,
+    },
+    /// The client configuration is invalid.
+    Config(String),
+    /// The blocking runtime could not be started.
+    Runtime(std::io::Error),
+}
+
+impl ServiceError {
+    /// Builds the error for a failed response from its status and body.
+    pub fn from_response(status: u16, body: &str) -> Self {
+        match serde_json::from_str::<ErrorBody>(body) {
+            Ok(body) => Self::Service {
+                status,
+                code: ErrorCode::from_code(&body.code),
+                message: body.message,
+                stage: body.stage,
+            },
+            Err(_) => Self::Service {
+                status,
+                code: match status {
+                    503 => ErrorCode::Unavailable,
+                    _ => ErrorCode::Rejected,
+                },
+                message: body.to_string(),
+                stage: None,
+            },
+        }
+    }
+
+    /// Returns the service error code, if the service answered.
+    pub fn code(&self) -> Option<&ErrorCode> {
+        match self {
+            Self::Service { code, .. } => Some(code),
+            _ => None,
+        }
+    }
+
+    /// Whether the service did not take the request: a connection failure or a 503.
+    pub fn is_unavailable(&self) -> bool {
+        match self {
+            Self::Service { status, .. } => *status == 503,
+            Self::Transport(error) => error.is_connect(),
+            _ => false,
+        }
+    }
+
+    /// Whether another attempt may succeed: timeouts, connection failures and 5xx statuses,
+    /// except for the errors a retry would only repeat. A failed broadcast is not retried,
+    /// because another attempt would prove the transaction again.
+    pub fn is_retryable(&self) -> bool {
+        match self {
+            Self::Service { status, code, .. } => {
+                *status >= 500
+                    && !matches!(
+                        code,
+                        ErrorCode::VerificationFailed
+                            | ErrorCode::InvalidTransaction
+                            | ErrorCode::BroadcastFailed
+                    )
+            }
+            Self::Timeout => true,
+            Self::Transport(error) => error.is_connect(),
+            Self::Decode(_)
+            | Self::Unconfirmed { .. }
+            | Self::Dropped { .. }
+            | Self::Config(_)
+            | Self::Runtime(_) => false,
+        }
+    }
+}
+
+impl fmt::Display for ServiceError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Self::Service {
+                status,
+                code,
+                message,
+                ..
+            } => write!(
+                f,
+                "The service failed with status {status} ({code:?}): {message}"
+            ),
+            Self::Timeout => write!(f, "The request timed out"),
+            Self::Transport(error) => write!(f, "The request failed: {error}"),
+            Self::Decode(message) => write!(f, "The response could not be decoded: {message}"),
+            Self::Unconfirmed {
+                transaction_id,
+                waited,
+            } => write!(
+                f,
+                "Transaction {transaction_id} was not confirmed within {waited:?}"
+            ),
+            Self::Dropped {
+                transaction_id,
+                waited,
+            } => write!(
+                f,
+                "Transaction {transaction_id} is unknown to the node after {waited:?}"
+            ),
+            Self::Config(message) => write!(f, "Invalid client configuration: {message}"),
+            Self::Runtime(error) => write!(f, "The runtime could not be started: {error}"),
+        }
+    }
+}
+
+impl std::error::Error for ServiceError {}
+
+impl From<reqwest::Error> for ServiceError {
+    fn from(error: reqwest::Error) -> Self {
+        match error.is_timeout() {
+            true => Self::Timeout,
+            false => Self::Transport(error),
+        }
+    }
+}
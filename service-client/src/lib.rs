@@ -0,0 +1,44 @@
+// Copyright (C) 2019-2024 Aleo Systems Inc.
+// This file is part of the Aleo SDK library.
+
+// The Aleo SDK library is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// The Aleo SDK library is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with the Aleo SDK library. If not, see <https://www.gnu.org/licenses/>.
+
+pub mod blocking;
+pub use blocking::*;
+
+pub mod client;
+pub use client::*;
+
+pub mod confirm;
+pub use confirm::*;
+
+pub mod config;
+pub use config::*;
+
+pub mod error;
+pub use error::*;
+
+pub mod replicas;
+pub use replicas::*;
+
+pub use authorize_service::{
+    AuthorizeRequest, AuthorizeResponse, SignRequest, SignResponse, VerifyRequest, VerifyResponse,
+};
+pub use execute_service::ExecuteRequest;
+
+use snarkvm::ledger::block::Transaction;
+use snarkvm::prelude::{
+    Address, CanaryV0, Deserialize, Field, FromBytes, MainnetV0, Network, PrivateKey, Program,
+    ProgramID, Serialize, TestnetV0, ToBytes,
+};
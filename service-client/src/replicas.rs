@@ -0,0 +1,208 @@
+// Copyright (C) 2019-2024 Aleo Systems Inc.
+// This file is part of the Aleo SDK library.
+
+// The Aleo SDK library is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// The Aleo SDK library is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with the Aleo SDK library. If not, see <https://www.gnu.org/licenses/>.
+
+use super::*;
+
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+
+/// The state of an execute service replica, as seen by this client.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ReplicaStatus {
+    pub url: String,
+    /// The requests this client has in flight on the replica.
+    pub outstanding: usize,
+    /// Whether the replica is usable, i.e. not cooling down after a failure.
+    pub healthy: bool,
+}
+
+/// The execute service replicas, with the requests in flight on each and their cooldowns.
+#[derive(Debug)]
+pub(crate) struct Replicas {
+    replicas: Vec<Replica>,
+    probed_at: Mutex<Option<Instant>>,
+}
+
+#[derive(Debug)]
+struct Replica {
+    url: String,
+    outstanding: AtomicUsize,
+    cooldown_until: Mutex<Option<Instant>>,
+}
+
+/// Counts a request as outstanding on a replica until it is dropped.
+pub(crate) struct Outstanding<'a>(&'a AtomicUsize);
+
+impl Drop for Outstanding<'_> {
+    fn drop(&mut self) {
+        self.0.fetch_sub(1, Ordering::SeqCst);
+    }
+}
+
+impl Replicas {
+    pub(crate) fn new(urls: &[String]) -> Self {
+        Self {
+            replicas: urls
+                .iter()
+                .map(|url| Replica {
+                    url: url.clone(),
+                    outstanding: AtomicUsize::new(0),
+                    cooldown_until: Mutex::new(None),
+                })
+                .collect(),
+            probed_at: Mutex::new(None),
+        }
+    }
+
+    pub(crate) fn len(&self) -> usize {
+        self.replicas.len()
+    }
+
+    pub(crate) fn url(&self, index: usize) -> &str {
+        &self.replicas[index].url
+    }
+
+    /// Picks a replica other than the `excluded` ones, preferring those that are not cooling
+    /// down, then those with the fewest outstanding requests. If every candidate is cooling
+    /// down, the one that recovers first is picked. At least one replica must not be excluded.
+    pub(crate) fn pick(&self, excluded: &[usize]) -> usize {
+        let now = Instant::now();
+        (0..self.replicas.len())
+            .filter(|index| !excluded.contains(index))
+            .min_by_key(|&index| {
+                let replica = &self.replicas[index];
+                let cooldown_until = replica.cooldown_until().filter(|until| *until > now);
+                (
+                    cooldown_until.is_some(),
+                    cooldown_until,
+                    replica.outstanding.load(Ordering::SeqCst),
+                )
+            })
+            .expect("Every replica is excluded")
+    }
+
+    /// Counts a request as outstanding on the replica.
+    pub(crate) fn start(&self, index: usize) -> Outstanding<'_> {
+        let outstanding = &self.replicas[index].outstanding;
+        outstanding.fetch_add(1, Ordering::SeqCst);
+        Outstanding(outstanding)
+    }
+
+    /// Skips the replica for `cooldown`. `pick` still returns it if every candidate is
+    /// cooling down.
+    pub(crate) fn mark_unhealthy(&self, index: usize, cooldown: Duration) {
+        *self.replicas[index].cooldown_until.lock().unwrap() = Some(Instant::now() + cooldown);
+    }
+
+    /// Ends the cooldown of the replica.
+    pub(crate) fn mark_healthy(&self, index: usize) {
+        *self.replicas[index].cooldown_until.lock().unwrap() = None;
+    }
+
+    /// Whether the replicas were never probed, or last probed more than `interval` ago.
+    /// A `true` result counts as a probe, so that concurrent requests probe only once.
+    pub(crate) fn probe_due(&self, interval: Duration) -> bool {
+        let mut probed_at = self.probed_at.lock().unwrap();
+        let now = Instant::now();
+        match *probed_at {
+            Some(at) if now.duration_since(at) < interval => false,
+            _ => {
+                *probed_at = Some(now);
+                true
+            }
+        }
+    }
+
+    pub(crate) fn status(&self) -> Vec<ReplicaStatus> {
+        let now = Instant::now();
+        self.replicas
+            .iter()
+            .map(|replica| ReplicaStatus {
+                url: replica.url.clone(),
+                outstanding: replica.outstanding.load(Ordering::SeqCst),
+                healthy: replica
+                    .cooldown_until()
+                    .filter(|until| *until > now)
+                    .is_none(),
+            })
+            .collect()
+    }
+}
+
+impl Replica {
+    fn cooldown_until(&self) -> Option<Instant> {
+        *self.cooldown_until.lock().unwrap()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn sample_replicas() -> Replicas {
+        Replicas::new(&["http://a".to_string(), "http://b".to_string()])
+    }
+
+    #[test]
+    fn test_picks_least_outstanding() {
+        let replicas = sample_replicas();
+        assert_eq!(replicas.pick(&[]), 0);
+        let outstanding = replicas.start(0);
+        assert_eq!(replicas.pick(&[]), 1);
+        let _other = replicas.start(1);
+        let _more = replicas.start(1);
+        assert_eq!(replicas.pick(&[]), 0);
+        drop(outstanding);
+        assert_eq!(replicas.status()[0].outstanding, 0);
+        assert_eq!(replicas.pick(&[0]), 1);
+    }
+
+    #[test]
+    fn test_skips_replicas_cooling_down() {
+        let replicas = sample_replicas();
+        replicas.mark_unhealthy(0, Duration::from_secs(60));
+        assert_eq!(replicas.pick(&[]), 1);
+        assert!(!replicas.status()[0].healthy);
+
+        // Without another choice, the replica that recovers first is picked.
+        replicas.mark_unhealthy(1, Duration::from_secs(120));
+        assert_eq!(replicas.pick(&[]), 0);
+        assert_eq!(replicas.pick(&[0]), 1);
+
+        replicas.mark_healthy(1);
+        assert_eq!(replicas.pick(&[]), 1);
+    }
+
+    #[test]
+    fn test_probe_due() {
+        let replicas = sample_replicas();
+        assert!(replicas.probe_due(Duration::from_millis(10)));
+        assert!(!replicas.probe_due(Duration::from_millis(10)));
+        std::thread::sleep(Duration::from_millis(20));
+        assert!(replicas.probe_due(Duration::from_millis(10)));
+    }
+
+    #[test]
+    fn test_cooldown_expires() {
+        let replicas = sample_replicas();
+        replicas.mark_unhealthy(0, Duration::from_millis(10));
+        assert_eq!(replicas.pick(&[]), 1);
+        std::thread::sleep(Duration::from_millis(20));
+        assert_eq!(replicas.pick(&[]), 0);
+        assert!(replicas.status()[0].healthy);
+    }
+}
@@ -0,0 +1,295 @@
+// Copyright (C) 2019-2024 Aleo Systems Inc.
+// This file is part of the Aleo SDK library.
+
+// The Aleo SDK library is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// The Aleo SDK library is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with the Aleo SDK library. If not, see <https://www.gnu.org/licenses/>.
+
+use anyhow::{anyhow, ensure, Result};
+use serde::{de::DeserializeOwned, Deserialize, Serialize};
+use std::fmt::Display;
+use std::io::Write;
+use std::path::Path;
+use std::str::FromStr;
+
+/// The networks a service can run on.
+pub const NETWORKS: [&str; 3] = ["mainnet", "testnet", "canary"];
+
+/// The format of the log lines written to stderr.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum LogFormat {
+    /// Colored, human-readable lines.
+    #[default]
+    Pretty,
+    /// One JSON object per line.
+    Json,
+}
+
+impl FromStr for LogFormat {
+    type Err = anyhow::Error;
+
+    fn from_str(format: &str) -> Result<Self> {
+        match format {
+            "pretty" => Ok(Self::Pretty),
+            "json" => Ok(Self::Json),
+            _ => Err(anyhow!(
+                "Invalid log format '{format}', expected 'pretty' or 'json'"
+            )),
+        }
+    }
+}
+
+impl LogFormat {
+    /// Installs the global logger. The level is still taken from `RUST_LOG`.
+    pub fn init(self) {
+        match self {
+            Self::Pretty => pretty_env_logger::init(),
+            Self::Json => env_logger::Builder::from_default_env()
+                .format(|buf, record| {
+                    let line = serde_json::json!({
+                        "level": record.level().as_str(),
+                        "target": record.target(),
+                        "message": record.args().to_string(),
+                    });
+                    writeln!(buf, "{line}")
+                })
+                .init(),
+        }
+    }
+}
+
+/// The settings of one service, read from its section of the configuration file.
+pub trait ServiceConfig: Default + Serialize + DeserializeOwned {
+    /// The name of the section, e.g. `execute`.
+    const SECTION: &'static str;
+
+    /// Overrides the configured values with the `ALEO_*` environment variables.
+    fn apply_env(&mut self) -> Result<()>;
+
+    /// Checks that the settings can be used to start the service.
+    fn validate(&self) -> Result<()>;
+}
+
+/// The configuration file shared by the authorize and execute services.
+/// Each service reads the top-level keys and its own section, and ignores the other sections.
+#[derive(Clone, Debug, Default)]
+pub struct Config<S> {
+    /// The network to run on: `mainnet`, `testnet` or `canary`.
+    pub network: Option<String>,
+    /// The format of the log lines.
+    pub log_format: LogFormat,
+    /// The settings of the service.
+    pub service: S,
+}
+
+// The top-level keys of the configuration file.
+#[derive(Default, Deserialize)]
+#[serde(default)]
+struct TopLevel {
+    network: Option<String>,
+    log_format: LogFormat,
+}
+
+impl<S: ServiceConfig> Config<S> {
+    /// Reads the configuration file, if one is given, and applies the environment overrides.
+    pub fn load(path: Option<&Path>) -> Result<Self> {
+        let mut config = match path {
+            Some(path) => {
+                let contents = std::fs::read_to_string(path)
+                    .map_err(|error| anyhow!("Failed to read {}: {error}", path.display()))?;
+                Self::from_toml(&contents)
+                    .map_err(|error| anyhow!("Failed to parse {}: {error}", path.display()))?
+            }
+            None => Self::default(),
+        };
+        config.apply_env()?;
+        Ok(config)
+    }
+
+    /// Parses the top-level keys and the section of the service from TOML.
+    pub fn from_toml(contents: &str) -> Result<Self> {
+        let mut table = toml::from_str::<toml::Table>(contents)?;
+        let service = match table.remove(S::SECTION) {
+            Some(section) => section
+                .try_into()
+                .map_err(|error| anyhow!("Invalid [{}] section: {error}", S::SECTION))?,
+            None => S::default(),
+        };
+        let top_level = toml::Value::Table(table).try_into::<TopLevel>()?;
+        Ok(Self {
+            network: top_level.network,
+            log_format: top_level.log_format,
+            service,
+        })
+    }
+
+    /// Overrides the configured values with the `ALEO_*` environment variables.
+    pub fn apply_env(&mut self) -> Result<()> {
+        env_option("ALEO_NETWORK", &mut self.network)?;
+        env_value("ALEO_LOG_FORMAT", &mut self.log_format)?;
+        self.service.apply_env()
+    }
+
+    /// Checks that the effective configuration can be used to start the service.
+    pub fn validate(&self) -> Result<()> {
+        match &self.network {
+            Some(network) => ensure!(
+                NETWORKS.contains(&network.as_str()),
+                "Invalid network '{network}', expected one of {NETWORKS:?}"
+            ),
+            None => return Err(anyhow!("No network is configured")),
+        }
+        self.service.validate()
+    }
+
+    /// Renders the effective configuration as TOML.
+    pub fn to_toml(&self) -> Result<String> {
+        let mut table = toml::Table::new();
+        if let Some(network) = &self.network {
+            table.insert("network".to_string(), network.clone().into());
+        }
+        table.insert(
+            "log_format".to_string(),
+            toml::Value::try_from(self.log_format)?,
+        );
+        table.insert(
+            S::SECTION.to_string(),
+            toml::Value::try_from(&self.service)?,
+        );
+        Ok(toml::to_string_pretty(&table)?)
+    }
+}
+
+/// Replaces the value with the parsed environment variable, if it is set.
+pub fn env_value<T: FromStr>(key: &str, value: &mut T) -> Result<()>
+where
+    T::Err: Display,
+{
+    if let Ok(raw) = std::env::var(key) {
+        *value = raw
+            .parse()
+            .map_err(|error| anyhow!("Invalid value '{raw}' for {key}: {error}"))?;
+    }
+    Ok(())
+}
+
+/// Replaces the optional value with the parsed environment variable, if it is set.
+pub fn env_option<T: FromStr>(key: &str, value: &mut Option<T>) -> Result<()>
+where
+    T::Err: Display,
+{
+    if let Ok(raw) = std::env::var(key) {
+        *value = Some(
+            raw.parse()
+                .map_err(|error| anyhow!("Invalid value '{raw}' for {key}: {error}"))?,
+        );
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+    #[serde(default, deny_unknown_fields)]
+    struct TestConfig {
+        port: u16,
+        grpc_port: Option<u16>,
+    }
+
+    impl Default for TestConfig {
+        fn default() -> Self {
+            Self {
+                port: 8080,
+                grpc_port: None,
+            }
+        }
+    }
+
+    impl ServiceConfig for TestConfig {
+        const SECTION: &'static str = "test";
+
+        fn apply_env(&mut self) -> Result<()> {
+            env_value("ALEO_COMMON_TEST_PORT", &mut self.port)
+        }
+
+        fn validate(&self) -> Result<()> {
+            ensure!(self.port != 0, "The test port must not be 0");
+            Ok(())
+        }
+    }
+
+    #[test]
+    fn test_reads_own_section_and_ignores_others() {
+        let config = Config::<TestConfig>::from_toml(
+            "network = \"testnet\"\nlog_format = \"json\"\n[test]\nport = 9000\n[other]\nport = 1\n",
+        )
+        .unwrap();
+        assert_eq!(config.network.as_deref(), Some("testnet"));
+        assert_eq!(config.log_format, LogFormat::Json);
+        assert_eq!(config.service.port, 9000);
+        assert_eq!(config.service.grpc_port, None);
+    }
+
+    #[test]
+    fn test_rejects_unknown_key_in_own_section() {
+        assert!(Config::<TestConfig>::from_toml("[test]\nprot = 9000\n").is_err());
+    }
+
+    #[test]
+    fn test_rendered_config_parses_back() {
+        let config = Config {
+            network: Some("canary".to_string()),
+            log_format: LogFormat::Json,
+            service: TestConfig {
+                port: 9000,
+                grpc_port: Some(9001),
+            },
+        };
+        let parsed = Config::<TestConfig>::from_toml(&config.to_toml().unwrap()).unwrap();
+        assert_eq!(parsed.network, config.network);
+        assert_eq!(parsed.log_format, config.log_format);
+        assert_eq!(parsed.service, config.service);
+    }
+
+    #[test]
+    fn test_validate_checks_network_and_section() {
+        let mut config = Config::<TestConfig>::default();
+        assert!(config.validate().is_err());
+        config.network = Some("devnet".to_string());
+        assert!(config.validate().is_err());
+        config.network = Some("mainnet".to_string());
+        assert!(config.validate().is_ok());
+        config.service.port = 0;
+        assert!(config.validate().is_err());
+    }
+
+    #[test]
+    fn test_env_overrides_file() {
+        std::env::set_var("ALEO_COMMON_TEST_PORT", "9100");
+        let mut config = Config::<TestConfig>::from_toml("[test]\nport = 9000\n").unwrap();
+        config.apply_env().unwrap();
+        std::env::remove_var("ALEO_COMMON_TEST_PORT");
+        assert_eq!(config.service.port, 9100);
+    }
+
+    #[test]
+    fn test_invalid_env_value_is_reported() {
+        std::env::set_var("ALEO_COMMON_TEST_INVALID", "not a port");
+        let mut port = 8080u16;
+        let error = env_value("ALEO_COMMON_TEST_INVALID", &mut port).unwrap_err();
+        std::env::remove_var("ALEO_COMMON_TEST_INVALID");
+        assert!(error.to_string().contains("ALEO_COMMON_TEST_INVALID"));
+    }
+}
@@ -0,0 +1,161 @@
+// Copyright (C) 2019-2024 Aleo Systems Inc.
+// This file is part of the Aleo SDK library.
+
+// The Aleo SDK library is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// The Aleo SDK library is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with the Aleo SDK library. If not, see <https://www.gnu.org/licenses/>.
+
+use super::*;
+
+use anyhow::Result;
+use prometheus::core::Collector;
+use prometheus::{
+    Encoder, HistogramOpts, HistogramVec, IntCounterVec, IntGauge, Opts, Registry, TextEncoder,
+};
+use std::time::Duration;
+use warp::{Filter, Rejection, Reply};
+
+/// The Prometheus metrics exported by every service.
+pub struct ServiceMetrics {
+    registry: Registry,
+    /// The number of requests, by route and outcome.
+    pub requests: IntCounterVec,
+    /// The duration of each phase, by phase, program and function.
+    pub phase_duration: HistogramVec,
+    /// The number of requests waiting for a worker thread.
+    pub queue_depth: IntGauge,
+    /// The number of worker threads with a loaded process.
+    pub loaded_processes: IntGauge,
+}
+
+impl ServiceMetrics {
+    /// Registers the metrics under `namespace`, timing phases with the given buckets in seconds.
+    pub fn new(namespace: &str, buckets: &[f64]) -> Result<Self> {
+        let registry = Registry::new_custom(Some(namespace.to_string()), None)?;
+        let requests = IntCounterVec::new(
+            Opts::new(
+                "requests_total",
+                "The number of requests by route and outcome",
+            ),
+            &["route", "outcome"],
+        )?;
+        let phase_duration = HistogramVec::new(
+            HistogramOpts::new("phase_duration_seconds", "The duration of each phase")
+                .buckets(buckets.to_vec()),
+            &["phase", "program", "function"],
+        )?;
+        let queue_depth = IntGauge::new(
+            "queue_depth",
+            "The number of requests waiting for a worker thread",
+        )?;
+        let loaded_processes = IntGauge::new(
+            "loaded_processes",
+            "The number of worker threads with a loaded process",
+        )?;
+        registry.register(Box::new(requests.clone()))?;
+        registry.register(Box::new(phase_duration.clone()))?;
+        registry.register(Box::new(queue_depth.clone()))?;
+        registry.register(Box::new(loaded_processes.clone()))?;
+        Ok(Self {
+            registry,
+            requests,
+            phase_duration,
+            queue_depth,
+            loaded_processes,
+        })
+    }
+
+    /// Registers a metric specific to one service.
+    pub fn register<C: Collector + Clone + 'static>(&self, collector: &C) -> Result<()> {
+        Ok(self.registry.register(Box::new(collector.clone()))?)
+    }
+
+    /// Counts a request to `route` with the given outcome.
+    pub fn record_request(&self, route: &str, outcome: &str) {
+        self.requests.with_label_values(&[route, outcome]).inc();
+    }
+
+    /// Records the duration of a phase of `program/function`.
+    /// Only record phases that succeeded, so that the labels name functions the process knows
+    /// rather than whatever a request asked for.
+    pub fn observe_phase(&self, phase: &str, program: &str, function: &str, duration: Duration) {
+        self.phase_duration
+            .with_label_values(&[phase, program, function])
+            .observe(duration.as_secs_f64());
+    }
+
+    /// Encodes the metrics in the Prometheus text format.
+    pub fn encode(&self) -> Result<String> {
+        let mut buffer = Vec::new();
+        TextEncoder::new().encode(&self.registry.gather(), &mut buffer)?;
+        Ok(String::from_utf8(buffer)?)
+    }
+}
+
+// GET /metrics
+pub fn metrics_route(
+    metrics: &'static ServiceMetrics,
+) -> impl Filter<Extract = impl Reply, Error = Rejection> + Clone {
+    warp::get()
+        .and(warp::path("metrics"))
+        .and(warp::path::end())
+        .and_then(move || async move {
+            match metrics.encode() {
+                Ok(body) => Ok(warp::reply::with_header(
+                    body,
+                    "content-type",
+                    "text/plain; version=0.0.4",
+                )),
+                Err(_) => Err(warp::reject()),
+            }
+        })
+}
+
+/// Runs `f` on the rayon pool, counting it in the queue depth until a worker picks it up.
+/// The job counts as in flight until it finishes, so that shutdown can wait for it.
+pub async fn spawn_worker<T: Send + 'static>(
+    metrics: &ServiceMetrics,
+    f: impl FnOnce() -> T + Send + 'static,
+) -> T {
+    let guard = InFlightGuard::new();
+    let queue_depth = metrics.queue_depth.clone();
+    queue_depth.inc();
+    tokio_rayon::spawn_fifo(move || {
+        let _guard = guard;
+        queue_depth.dec();
+        f()
+    })
+    .await
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn test_spawn_worker_tracks_queue_depth() {
+        let metrics = ServiceMetrics::new("test_service", &[0.1, 1.0]).unwrap();
+        assert_eq!(spawn_worker(&metrics, || 42).await, 42);
+        assert_eq!(metrics.queue_depth.get(), 0);
+        assert_eq!(in_flight(), 0);
+    }
+
+    #[tokio::test]
+    async fn test_encode_includes_the_namespace() {
+        let metrics = ServiceMetrics::new("test_service", &[0.1, 1.0]).unwrap();
+        metrics.record_request("execute", "success");
+        let body = metrics.encode().unwrap();
+        assert!(
+            body.contains("test_service_requests_total{outcome=\"success\",route=\"execute\"} 1")
+        );
+    }
+}
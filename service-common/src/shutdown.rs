@@ -0,0 +1,175 @@
+// Copyright (C) 2019-2024 Aleo Systems Inc.
+// This file is part of the Aleo SDK library.
+
+// The Aleo SDK library is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// The Aleo SDK library is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with the Aleo SDK library. If not, see <https://www.gnu.org/licenses/>.
+
+use std::future::Future;
+use std::sync::atomic::{AtomicBool, AtomicUsize, Ordering};
+use std::time::{Duration, Instant};
+use warp::{Filter, Rejection, Reply};
+
+// Whether the service is shutting down and no longer accepts new work.
+static DRAINING: AtomicBool = AtomicBool::new(false);
+// The number of jobs queued or running on the worker pool.
+static IN_FLIGHT: AtomicUsize = AtomicUsize::new(0);
+
+/// Returns `true` once the service has started shutting down.
+pub fn is_draining() -> bool {
+    DRAINING.load(Ordering::SeqCst)
+}
+
+/// Returns the number of jobs queued or running on the worker pool.
+pub fn in_flight() -> usize {
+    IN_FLIGHT.load(Ordering::SeqCst)
+}
+
+/// Counts a job as in flight until the guard is dropped.
+pub struct InFlightGuard(());
+
+impl InFlightGuard {
+    pub fn new() -> Self {
+        IN_FLIGHT.fetch_add(1, Ordering::SeqCst);
+        Self(())
+    }
+}
+
+impl Default for InFlightGuard {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Drop for InFlightGuard {
+    fn drop(&mut self) {
+        IN_FLIGHT.fetch_sub(1, Ordering::SeqCst);
+    }
+}
+
+/// The rejection of new work while the service is shutting down.
+#[derive(Debug)]
+pub struct ShuttingDown;
+
+impl warp::reject::Reject for ShuttingDown {}
+
+/// Rejects new work once the service is shutting down.
+pub fn accepting_work() -> impl Filter<Extract = (), Error = Rejection> + Clone {
+    warp::any()
+        .and_then(|| async {
+            match is_draining() {
+                true => Err(warp::reject::custom(ShuttingDown)),
+                false => Ok(()),
+            }
+        })
+        .untuple_one()
+}
+
+/// Answers requests rejected during shutdown with `503 Service Unavailable`.
+pub async fn handle_shutdown_rejection(rejection: Rejection) -> Result<impl Reply, Rejection> {
+    match rejection.find::<ShuttingDown>() {
+        Some(_) => Ok(warp::reply::with_status(
+            "The service is shutting down",
+            warp::http::StatusCode::SERVICE_UNAVAILABLE,
+        )),
+        None => Err(rejection),
+    }
+}
+
+/// Resolves once the process receives SIGINT or SIGTERM.
+pub async fn shutdown_signal() {
+    let interrupt = async {
+        tokio::signal::ctrl_c()
+            .await
+            .expect("Failed to listen for SIGINT");
+    };
+    #[cfg(unix)]
+    let terminate = async {
+        tokio::signal::unix::signal(tokio::signal::unix::SignalKind::terminate())
+            .expect("Failed to listen for SIGTERM")
+            .recv()
+            .await;
+    };
+    #[cfg(not(unix))]
+    let terminate = std::future::pending::<()>();
+
+    tokio::select! {
+        _ = interrupt => {},
+        _ = terminate => {},
+    }
+}
+
+/// Waits for a shutdown signal, then stops accepting new work and waits up to `timeout`
+/// for the queued and running jobs to finish.
+pub async fn drain(timeout: Duration) {
+    shutdown_signal().await;
+    DRAINING.store(true, Ordering::SeqCst);
+    log::info!("Shutting down, waiting for {} in-flight jobs", in_flight());
+
+    let deadline = Instant::now() + timeout;
+    while in_flight() > 0 && Instant::now() < deadline {
+        tokio::time::sleep(Duration::from_millis(100)).await;
+    }
+    match in_flight() {
+        0 => log::info!("All in-flight jobs finished"),
+        remaining => log::warn!("Abandoning {remaining} in-flight jobs after {timeout:?}"),
+    }
+}
+
+/// Runs the server until it shuts down gracefully, but no longer than `timeout` after a shutdown
+/// signal. Clients holding connections open cannot keep the process alive past the timeout.
+pub async fn serve_until_shutdown(server: impl Future<Output = ()>, timeout: Duration) {
+    tokio::pin!(server);
+    tokio::select! {
+        _ = &mut server => return,
+        _ = wait_for_draining() => {},
+    }
+    if tokio::time::timeout(timeout, server).await.is_err() {
+        log::warn!("Closing the connections still open {timeout:?} after shutdown");
+    }
+}
+
+/// Resolves once the service has started shutting down.
+pub async fn wait_for_draining() {
+    while !is_draining() {
+        tokio::time::sleep(Duration::from_millis(100)).await;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // Drains until dropped, so that later tests in this binary accept work again.
+    struct Draining;
+
+    impl Draining {
+        fn start() -> Self {
+            DRAINING.store(true, Ordering::SeqCst);
+            Self
+        }
+    }
+
+    impl Drop for Draining {
+        fn drop(&mut self) {
+            DRAINING.store(false, Ordering::SeqCst);
+        }
+    }
+
+    #[tokio::test]
+    async fn test_open_connections_do_not_delay_exit() {
+        let _draining = Draining::start();
+        let start = Instant::now();
+        serve_until_shutdown(std::future::pending(), Duration::from_millis(200)).await;
+        assert!(start.elapsed() < Duration::from_secs(2));
+    }
+}